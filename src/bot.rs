@@ -0,0 +1,200 @@
+use std::{
+    collections::BTreeMap,
+    io::{self, BufRead, Write},
+};
+
+use anyhow::Result;
+use serde_json::{json, Value};
+
+use crate::{
+    engine::Engine,
+    provider::{Solution, SolutionProvider},
+    scoring::WORD_LENGTH,
+    share, solver, AppMode, Options, Outcome,
+};
+
+pub const BOT_ANSWERS: [&str; 2] = ["one", "all"];
+
+struct Played {
+    solved: bool,
+    guesses: usize,
+}
+
+// Plays games with a program over stdin and stdout, one JSON message per line
+pub fn run(all: bool, options: &Options) -> Result<Outcome> {
+    let solutions: Vec<Solution> = if all {
+        solver::answers()
+            .into_iter()
+            .map(|answer| Solution {
+                answer: answer.to_string(),
+                ..Default::default()
+            })
+            .collect()
+    } else {
+        vec![SolutionProvider::default()
+            .with_seed(options.seed)
+            .get_answer(AppMode::Random)?]
+    };
+
+    let mut input = io::stdin().lock().lines();
+    let mut results = vec![];
+    for (i, solution) in solutions.iter().enumerate() {
+        let seed = options.seed.map(|seed| seed.wrapping_add(i as u64));
+        let mut engine = Engine::new(options.variant, solution, seed);
+        match play(&mut engine, i + 1, options.max_guesses, &mut input)? {
+            Some(played) => results.push(played),
+            None => break, // The bot stopped sending guesses
+        }
+    }
+
+    send(&summary(&results))?;
+    print_summary(&results);
+
+    let outcome = if results.len() < solutions.len() {
+        Outcome::Quit
+    } else if results.iter().all(|played| played.solved) {
+        Outcome::Won
+    } else {
+        Outcome::Lost
+    };
+    Ok(outcome)
+}
+
+// Returns None if the input ends before the game does
+fn play(
+    engine: &mut Engine,
+    game: usize,
+    max_guesses: Option<usize>,
+    input: &mut impl Iterator<Item = io::Result<String>>,
+) -> Result<Option<Played>> {
+    send(&json!({
+        "type": "start",
+        "game": game,
+        "length": WORD_LENGTH,
+        "max_guesses": max_guesses,
+    }))?;
+
+    let mut guesses = 0;
+    while max_guesses.is_none_or(|max| guesses < max) {
+        let Some(line) = input.next() else {
+            return Ok(None);
+        };
+        let guess = match parse_guess(&line?) {
+            Ok(Some(guess)) => guess,
+            Ok(None) => continue,
+            Err(message) => {
+                send(&json!({"type": "error", "message": message}))?;
+                continue;
+            }
+        };
+
+        // Invalid guesses don't use up a turn
+        if !engine.is_valid(&guess) {
+            send(&json!({"type": "invalid", "guess": guess}))?;
+            continue;
+        }
+
+        let states = engine.score(&guess);
+        guesses += 1;
+        send(&json!({
+            "type": "feedback",
+            "guess": guess,
+            "pattern": share::pattern(&states),
+            "guesses": guesses,
+        }))?;
+
+        if engine.is_solved(&guess) {
+            send(&end(engine, true, guesses))?;
+            return Ok(Some(Played {
+                solved: true,
+                guesses,
+            }));
+        }
+    }
+
+    send(&end(engine, false, guesses))?;
+    Ok(Some(Played {
+        solved: false,
+        guesses,
+    }))
+}
+
+// Accepts `{"guess":"crane"}` or just the word
+fn parse_guess(line: &str) -> Result<Option<String>, String> {
+    let line = line.trim();
+    if line.is_empty() {
+        return Ok(None);
+    }
+
+    if !line.starts_with('{') {
+        return Ok(Some(line.to_ascii_lowercase()));
+    }
+    let msg: Value = serde_json::from_str(line).map_err(|e| format!("Invalid JSON: {}", e))?;
+    msg["guess"]
+        .as_str()
+        .map(|guess| Some(guess.trim().to_ascii_lowercase()))
+        .ok_or_else(|| "Expected a \"guess\" field".to_string())
+}
+
+fn end(engine: &Engine, solved: bool, guesses: usize) -> Value {
+    json!({
+        "type": "end",
+        "solved": solved,
+        "guesses": guesses,
+        "answer": engine.reveal(),
+    })
+}
+
+// Number of solved games for each number of guesses
+fn distribution(results: &[Played]) -> BTreeMap<usize, usize> {
+    let mut distribution = BTreeMap::new();
+    for played in results.iter().filter(|played| played.solved) {
+        *distribution.entry(played.guesses).or_default() += 1;
+    }
+    distribution
+}
+
+fn average(results: &[Played]) -> Option<f64> {
+    let solved: Vec<&Played> = results.iter().filter(|played| played.solved).collect();
+    if solved.is_empty() {
+        return None;
+    }
+    let total: usize = solved.iter().map(|played| played.guesses).sum();
+    Some(total as f64 / solved.len() as f64)
+}
+
+fn summary(results: &[Played]) -> Value {
+    let solved = results.iter().filter(|played| played.solved).count();
+    json!({
+        "type": "summary",
+        "games": results.len(),
+        "solved": solved,
+        "failed": results.len() - solved,
+        "average_guesses": average(results),
+        "distribution": distribution(results),
+    })
+}
+
+// Readable summary on stderr, keeping stdout for the protocol
+fn print_summary(results: &[Played]) {
+    let solved = results.iter().filter(|played| played.solved).count();
+    eprintln!("Games:    {}", results.len());
+    eprintln!("Solved:   {}", solved);
+    eprintln!("Failed:   {}", results.len() - solved);
+    if let Some(average) = average(results) {
+        eprintln!("Average:  {:.3} guesses", average);
+    }
+
+    let distribution = distribution(results);
+    let most = distribution.values().max().copied().unwrap_or_default();
+    for (guesses, count) in &distribution {
+        let bar = "#".repeat((count * 40).div_ceil(most));
+        eprintln!("{:>3} {:>6} {}", guesses, count, bar);
+    }
+}
+
+fn send(msg: &Value) -> io::Result<()> {
+    let mut stdout = io::stdout().lock();
+    writeln!(stdout, "{}", msg)?;
+    stdout.flush()
+}
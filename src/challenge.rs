@@ -0,0 +1,128 @@
+use anyhow::{bail, Context, Result};
+
+use crate::solver;
+
+// Crockford's base 32, which avoids letters that are easily confused
+const ALPHABET: &[u8; 32] = b"0123456789ABCDEFGHJKMNPQRSTVWXYZ";
+const CODE_DIGITS: usize = 4;
+const CODE_SPACE: u64 = 32_u64.pow(CODE_DIGITS as u32);
+
+// Scrambles word indices so neighbouring words get unrelated codes
+// The multiplier is odd, so it has an inverse modulo the code space
+const MULTIPLIER: u64 = 637_429;
+const OFFSET: u64 = 91_283;
+
+// Code for any valid word, e.g. "K3M9Q"
+pub fn create(word: &str) -> Result<String> {
+    let word = word.to_ascii_lowercase();
+    let idx = solver::all_words()
+        .binary_search(&word.as_str())
+        .ok()
+        .with_context(|| format!("\"{}\" is not in the word lists", word))?;
+
+    let mut n = (idx as u64 * MULTIPLIER + OFFSET) % CODE_SPACE;
+    let mut digits = [0_u8; CODE_DIGITS];
+    for digit in digits.iter_mut().rev() {
+        *digit = (n % 32) as u8;
+        n /= 32;
+    }
+
+    let mut code: String = digits
+        .iter()
+        .map(|d| ALPHABET[*d as usize] as char)
+        .collect();
+    code.push(ALPHABET[checksum(&digits)] as char);
+    Ok(code)
+}
+
+pub fn decode(code: &str) -> Result<String> {
+    let chars = code.trim().to_ascii_uppercase().chars().collect::<Vec<_>>();
+    if chars.len() != CODE_DIGITS + 1 {
+        bail!("Challenge codes are {} characters long", CODE_DIGITS + 1);
+    }
+
+    let digits = chars
+        .iter()
+        .map(|ch| digit(*ch).with_context(|| format!("Invalid character '{}' in code", ch)))
+        .collect::<Result<Vec<_>>>()?;
+    let (value, check) = digits.split_at(CODE_DIGITS);
+    if check[0] as usize != checksum(value) {
+        bail!(
+            "Invalid challenge code \"{}\", check it was copied correctly",
+            code
+        );
+    }
+
+    let n = value.iter().fold(0, |acc, d| acc * 32 + *d as u64);
+    let idx = (n + CODE_SPACE - OFFSET) % CODE_SPACE * inverse(MULTIPLIER) % CODE_SPACE;
+
+    solver::all_words()
+        .get(idx as usize)
+        .map(|word| word.to_string())
+        .with_context(|| format!("Invalid challenge code \"{}\"", code))
+}
+
+// Accepts lowercase and the characters Crockford's base 32 treats as lookalikes
+fn digit(ch: char) -> Option<u8> {
+    let ch = match ch {
+        'O' => '0',
+        'I' | 'L' => '1',
+        _ => ch,
+    };
+    ALPHABET
+        .iter()
+        .position(|c| *c as char == ch)
+        .map(|d| d as u8)
+}
+
+// Catches typos, weighting each digit by position so swapped digits are detected
+fn checksum(digits: &[u8]) -> usize {
+    digits
+        .iter()
+        .enumerate()
+        .map(|(i, d)| (i + 1) * *d as usize)
+        .sum::<usize>()
+        % 31
+}
+
+// Multiplicative inverse modulo the code space, by Newton's method
+fn inverse(a: u64) -> u64 {
+    let mut x = a; // Correct to 3 bits, as a * a = 1 mod 8 for odd a
+    for _ in 0..5 {
+        x = x * (2 + CODE_SPACE * CODE_SPACE - a * x % CODE_SPACE) % CODE_SPACE;
+    }
+    x
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn codes_decode_to_their_word() {
+        for word in ["aback", "crane", "zonal", "xylyl"] {
+            let code = create(word).unwrap();
+            assert_eq!(code.len(), CODE_DIGITS + 1);
+            assert_eq!(decode(&code).unwrap(), word);
+            assert_eq!(decode(&code.to_ascii_lowercase()).unwrap(), word);
+        }
+    }
+
+    #[test]
+    fn rejects_invalid_codes() {
+        assert!(create("zzzzz").is_err());
+        assert!(decode("ABC").is_err());
+        assert!(decode("AB#DE").is_err());
+
+        // A changed digit fails the checksum
+        let code = create("crane").unwrap();
+        let mut changed: Vec<char> = code.chars().collect();
+        changed[0] = if changed[0] == '0' { '1' } else { '0' };
+        assert!(decode(&changed.into_iter().collect::<String>()).is_err());
+    }
+
+    #[test]
+    fn inverse_undoes_the_multiplier() {
+        assert_eq!(MULTIPLIER * inverse(MULTIPLIER) % CODE_SPACE, 1);
+    }
+}
@@ -0,0 +1,49 @@
+use std::{
+    env,
+    io::{self, IsTerminal, Write},
+};
+
+use anyhow::Result;
+use base64::{engine::general_purpose::STANDARD, Engine};
+
+// Terminals can't be queried for OSC 52 support, so rule out those known not to have it.
+// Set `TURDLE_OSC52=1` or `TURDLE_OSC52=0` to override the check.
+pub fn osc52_supported() -> bool {
+    if let Ok(s) = env::var("TURDLE_OSC52") {
+        return s == "1";
+    }
+
+    if !io::stdout().is_terminal() {
+        return false;
+    }
+
+    match env::var("TERM") {
+        Ok(term) => !(term.is_empty() || term == "dumb" || term == "linux"),
+        Err(_) => cfg!(windows) && env::var("WT_SESSION").is_ok(), // Windows Terminal
+    }
+}
+
+// Puts text on the system clipboard via the OSC 52 escape sequence
+// Returns false if the terminal doesn't support it
+pub fn copy(text: &str) -> Result<bool> {
+    if !osc52_supported() {
+        return Ok(false);
+    }
+
+    let seq = format!("\x1b]52;c;{}\x07", STANDARD.encode(text));
+
+    // Multiplexers need the sequence wrapped to pass it through to the outer terminal
+    let seq = if env::var("TMUX").is_ok() {
+        format!("\x1bPtmux;{}\x1b\\", seq.replace('\x1b', "\x1b\x1b"))
+    } else if matches!(env::var("TERM"), Ok(term) if term.starts_with("screen")) {
+        format!("\x1bP{}\x1b\\", seq)
+    } else {
+        seq
+    };
+
+    let mut stdout = io::stdout();
+    stdout.write_all(seq.as_bytes())?;
+    stdout.flush()?;
+
+    Ok(true)
+}
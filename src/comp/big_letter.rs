@@ -5,7 +5,7 @@ use tuirealm::{
     tui::{
         layout::Rect,
         style::Stylize,
-        widgets::{Block, Borders},
+        widgets::{Block, Borders, Paragraph},
     },
     AttrValue, Attribute, Frame, MockComponent, Props, State,
 };
@@ -17,9 +17,12 @@ pub struct BigLetter {
     props: Props,
     state: LetterState,
     value: Option<char>,
-    size: PixelSize,
+    size: Option<PixelSize>, // None for a plain letter on a single line
     bg: Option<u8>,
     revealed: bool, // Whether the letter has been validated, i.e. bg colour set
+    selected: bool, // Whether the player is marking this letter's colour
+    cursor: bool,   // Whether the next letter typed goes here
+    lie: bool,      // Whether this letter was shown with the wrong colour
 }
 
 impl BigLetter {
@@ -32,7 +35,7 @@ impl BigLetter {
         self
     }
 
-    pub fn with_size(mut self, size: PixelSize) -> Self {
+    pub fn with_size(mut self, size: Option<PixelSize>) -> Self {
         self.size = size;
         self
     }
@@ -47,6 +50,21 @@ impl BigLetter {
         self
     }
 
+    pub fn with_selected(mut self, selected: bool) -> Self {
+        self.selected = selected;
+        self
+    }
+
+    pub fn with_cursor(mut self, cursor: bool) -> Self {
+        self.cursor = cursor;
+        self
+    }
+
+    pub fn with_lie(mut self, lie: bool) -> Self {
+        self.lie = lie;
+        self
+    }
+
     pub fn with_colour(mut self) -> Self {
         self.revealed = true;
         self
@@ -70,30 +88,64 @@ impl MockComponent for BigLetter {
             let fg = theme::LETTER_FG;
 
             // Bottom border background to match window
-            let window_bg = if self.bg.is_some() {
-                Color::Indexed(self.bg.unwrap())
+            let window_bg = if let Some(bg) = self.bg {
+                Color::Indexed(bg)
             } else {
                 Color::Reset
             };
 
+            // Highlight selected letter or cursor with a contrasting bottom border
+            let border_fg = if self.selected || self.cursor {
+                theme::CELL_BORDER_SELECTED
+            } else if self.lie {
+                theme::CELL_BORDER_LIE
+            } else {
+                bg
+            };
+
+            let ch = self.value.unwrap_or_default().to_ascii_uppercase();
+            let Some(size) = self.size else {
+                // Compact tile, with the selected, cursor or lying letter picked out in colour
+                let style = if self.selected || self.cursor || self.lie {
+                    Style::default().fg(border_fg).underlined()
+                } else {
+                    Style::default().fg(fg)
+                };
+                let text = match self.value {
+                    Some(_) => ch.to_string(),
+                    None if self.cursor => "_".to_string(),
+                    None => String::new(),
+                };
+                let text = Paragraph::new(text)
+                    .style(style.bg(bg).bold())
+                    .alignment(Alignment::Center);
+                frame.render_widget(text, Rect { height: 1, ..area });
+                return;
+            };
+
             let block = Block::default()
                 .fg(fg)
                 .bg(bg)
                 .borders(Borders::BOTTOM)
-                .border_style(Style::default().fg(bg).bg(window_bg))
+                .border_style(Style::default().fg(border_fg).bg(window_bg))
                 .border_type(BorderType::QuadrantInside);
             frame.render_widget(block, area);
 
-            let ch = self.value.unwrap_or_default().to_ascii_uppercase();
             let big_text = BigText::builder()
-                .pixel_size(self.size)
+                .pixel_size(size)
                 .style(Style::default().white())
                 .alignment(Alignment::Center)
                 .lines(vec![ch.to_string().into()])
                 .build()
                 .expect("Could not build BigText");
+            // Padding above the letter if there's room, the border takes the bottom line
+            let letter_height = match size {
+                PixelSize::Full | PixelSize::HalfWidth => 8,
+                PixelSize::HalfHeight | PixelSize::Quadrant => 4,
+                PixelSize::ThirdHeight | PixelSize::Sextant => 3,
+            };
             let area = Rect {
-                y: area.y + 1,
+                y: area.y + area.height.saturating_sub(letter_height + 1),
                 ..area
             };
             frame.render_widget(big_text, area);
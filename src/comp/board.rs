@@ -9,28 +9,35 @@ use tui_big_text::PixelSize;
 use tuirealm::{
     command::{Cmd, CmdResult},
     event::{Key, KeyEvent, KeyModifiers},
-    props::Color,
+    props::{Alignment, Color},
     tui::{
         layout::{Constraint, Layout, Rect},
-        style::Stylize,
-        widgets::Block,
+        style::{Style, Stylize},
+        widgets::{Block, Paragraph},
     },
-    AttrValue, Attribute, Component, Event, Frame, MockComponent, NoUserEvent, Props, State,
+    AttrValue, Attribute, Component, Event, Frame, MockComponent, Props, State,
 };
 
 use crate::{
-    model::{LetterState, Msg},
+    engine::{Engine, Variant},
+    keymap::Action,
+    model::{LetterState, Msg, UserEvent},
     provider::Solution,
+    scoring::{self, is_valid_word, WORD_LENGTH},
+    solver::{self, Constraints},
     ResultGrid,
 };
 
-use super::word_line::{WordLine, WordLineState};
+use super::{
+    candidates::Hints,
+    letter_pool::{KeyAreas, PoolKey},
+    players::{player_colour, Players},
+    timer::Clock,
+    word_line::{WordLine, WordLineState},
+};
 
-const LETTER_SIZE: PixelSize = PixelSize::Sextant;
-const CELL_WIDTH: u16 = 10;
-const CELL_HEIGHT: u16 = 4;
-const CELL_VER_MARGIN: u16 = 1;
-const CELL_HOR_MARGIN: u16 = 1;
+pub const CELL_SIZE_ATTR: &str = "cell-size";
+const LABEL_WIDTH: u16 = 10;
 const ANIM_FRAME_DURATION: Duration = Duration::from_millis(50);
 const ANIM_STEP_VALUES: [i16; 8] = [1, 0, -1, 0, 1, 0, -1, 0];
 
@@ -43,15 +50,110 @@ pub struct Board {
     bg: Option<u8>,
     letter_states: Rc<RwLock<IndexMap<char, LetterState>>>,
     solution: Solution,
+    engine: Engine,
     anim_last_frame_index: usize,
     anim_last_frame_time: Instant,
+    started: Instant,
+    mode: BoardMode,
+    marking: Option<usize>, // Selected tile while the player enters feedback
+    constraints: Constraints,
+    hints: Rc<RwLock<Hints>>,
+    notice: Option<String>,               // Message for the status line
+    max_guesses: Option<usize>,           // None if unlimited
+    cell_size: CellSize,                  // Set by the model to fit the terminal
+    clock: Option<Rc<RwLock<Clock>>>,     // Set for timed games
+    players: Option<Rc<RwLock<Players>>>, // Set for hot-seat games
+    keys: KeyAreas,                       // On-screen keyboard, for mouse clicks
+    tiles: Vec<Rect>,                     // Where the active line's tiles were drawn
+}
+
+// Tile sizes from largest to smallest, the largest that fits the terminal is used
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub enum CellSize {
+    #[default]
+    Large, // Tall quadrant letters with a gap between rows
+    Medium,  // Shorter sextant letters with a gap between rows
+    Small,   // Sextant letters in narrower tiles, rows touching
+    Compact, // One line per row with plain letters, fits 24x80
+}
+
+impl CellSize {
+    pub const ALL: [CellSize; 4] = [
+        CellSize::Large,
+        CellSize::Medium,
+        CellSize::Small,
+        CellSize::Compact,
+    ];
+
+    pub fn width(self) -> u16 {
+        match self {
+            CellSize::Large | CellSize::Medium => 10,
+            CellSize::Small => 8,
+            CellSize::Compact => 3,
+        }
+    }
+
+    pub fn height(self) -> u16 {
+        match self {
+            CellSize::Large => 5,
+            CellSize::Medium | CellSize::Small => 4,
+            CellSize::Compact => 1,
+        }
+    }
+
+    fn ver_margin(self) -> u16 {
+        match self {
+            CellSize::Small => 0,
+            _ => 1,
+        }
+    }
+
+    fn hor_margin(self) -> u16 {
+        1
+    }
+
+    // None for plain letters without the big font
+    pub fn letter_size(self) -> Option<PixelSize> {
+        match self {
+            CellSize::Large => Some(PixelSize::Quadrant),
+            CellSize::Medium | CellSize::Small => Some(PixelSize::Sextant),
+            CellSize::Compact => None,
+        }
+    }
+
+    // Height of a board row including the gap below it
+    pub fn row_height(self) -> u16 {
+        self.height() + self.ver_margin()
+    }
+
+    pub fn board_width(self) -> u16 {
+        WORD_LENGTH as u16 * (self.width() + self.hor_margin())
+    }
+
+    // Largest size that shows `rows` rows in the area, or up to 6 if there are more
+    pub fn fit(width: u16, height: u16, rows: u16) -> Self {
+        let rows = rows.clamp(1, 6);
+        Self::ALL
+            .into_iter()
+            .find(|size| size.board_width() <= width && size.row_height() * rows <= height)
+            .unwrap_or(CellSize::Compact)
+    }
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub enum BoardMode {
+    #[default]
+    Play,
+    Assist,  // Answer is unknown and the player enters feedback from another game
+    Reverse, // Player thinks of an answer and enters feedback for the computer's guesses
 }
 
 #[derive(Debug, Default, Clone, PartialEq)]
 pub enum BoardState {
     #[default]
     Playing,
-    Failed,
+    Guessed(ResultGrid),
+    Failed(ResultGrid),
     Succeded(ResultGrid),
     Finished,
     Animating,
@@ -59,14 +161,25 @@ pub enum BoardState {
 
 impl Board {
     pub fn new(solution: &Solution) -> Self {
-        let lines = (0..6)
-            .map(|_| WordLine::default().with_answer(&solution.answer))
-            .collect();
+        let lines = (0..6).map(|_| WordLine::default()).collect();
 
         Self {
             lines,
             anim_last_frame_index: 0,
             anim_last_frame_time: Instant::now(),
+            started: Instant::now(),
+            mode: Default::default(),
+            marking: Default::default(),
+            constraints: Default::default(),
+            hints: Default::default(),
+            notice: Default::default(),
+            max_guesses: Some(6),
+            cell_size: Default::default(),
+            clock: Default::default(),
+            players: Default::default(),
+            keys: Default::default(),
+            tiles: Default::default(),
+            engine: Engine::new(Variant::Classic, solution, None),
             solution: solution.to_owned(),
             props: Default::default(),
             state: Default::default(),
@@ -81,7 +194,69 @@ impl Board {
         self
     }
 
+    pub fn with_key_areas(mut self, keys: KeyAreas) -> Self {
+        self.keys = keys;
+        self
+    }
+
+    pub fn with_engine(mut self, engine: Engine) -> Self {
+        self.engine = engine;
+        self
+    }
+
+    pub fn with_max_guesses(mut self, max_guesses: Option<usize>) -> Self {
+        self.max_guesses = max_guesses;
+        // Unlimited games start with a full board and add rows as needed
+        let rows = max_guesses.unwrap_or(6);
+        self.lines = (0..rows).map(|_| WordLine::default()).collect();
+        self
+    }
+
+    pub fn with_clock(mut self, clock: Rc<RwLock<Clock>>) -> Self {
+        self.clock = Some(clock);
+        self
+    }
+
+    pub fn with_players(mut self, players: Rc<RwLock<Players>>) -> Self {
+        self.players = Some(players);
+        self
+    }
+
+    pub fn with_mode(mut self, mode: BoardMode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    pub fn with_hints(mut self, hints: Rc<RwLock<Hints>>) -> Self {
+        self.hints = hints;
+        // The computer's first guess is the top suggestion
+        if self.mode == BoardMode::Reverse {
+            self.make_guess();
+        }
+        self
+    }
+
+    // Replays the guesses of a resumed game, leaving the last to be reported like a new guess
+    pub fn with_guesses(mut self, guesses: &[String]) -> Self {
+        for guess in guesses {
+            if self.state != BoardState::Playing {
+                break;
+            }
+            for ch in guess.chars() {
+                self.lines[self.active_line].push_char(ch);
+            }
+            self.handle_input_submit();
+            if matches!(self.state, BoardState::Guessed(_)) && guess != guesses.last().unwrap() {
+                self.state = BoardState::Playing;
+            }
+        }
+        self
+    }
+
     fn handle_input_char(&mut self, ch: char) -> CmdResult {
+        // The first keypress starts the clock
+        self.with_clock_mut(Clock::start);
+
         let line = self
             .lines
             .get_mut(self.active_line)
@@ -101,31 +276,68 @@ impl Board {
         CmdResult::None
     }
 
+    // Editing the guess with the cursor
+    fn handle_input_edit(&mut self, action: Action) -> CmdResult {
+        let line = self
+            .lines
+            .get_mut(self.active_line)
+            .expect("Could not get active word line");
+        match action {
+            Action::DeleteNext => line.del_next_char(),
+            Action::DeleteWord => line.del_word(),
+            Action::ClearLine => line.clear(),
+            Action::Left => line.move_cursor(false),
+            Action::Right => line.move_cursor(true),
+            _ => {}
+        }
+
+        CmdResult::None
+    }
+
     fn handle_input_submit(&mut self) -> CmdResult {
+        if self.mode != BoardMode::Play {
+            return self.handle_feedback_submit();
+        }
+
+        // Attribute the guess to the player whose turn it is
+        if let Some(player) = self.current_player() {
+            self.engine.set_player(player);
+            self.lines[self.active_line].set_player(Some(player));
+        }
+
         let line = self
             .lines
             .get_mut(self.active_line)
             .expect("Could not get active word line");
 
-        match line.submit() {
+        let res = line.submit(&mut self.engine);
+        if matches!(res, WordLineState::Correct(_) | WordLineState::Incorrect(_)) {
+            self.with_clock_mut(Clock::split);
+        }
+
+        match res {
             WordLineState::Correct(res) => {
                 self.update_letter_pool(res);
-                let result_grid = ResultGrid {
-                    solution: self.solution.clone(),
-                    lines_used: (self.active_line + 1) as u8,
-                    grid: self.lines
-                        .iter()
-                        .map(|line| line.get_letter_states())
-                        .collect(),
-            };
-                self.state = BoardState::Succeded(result_grid);
+                self.show_lies();
+                self.with_clock_mut(Clock::stop);
+                self.state = BoardState::Succeded(self.result_grid(true));
             }
             WordLineState::Incorrect(res) => {
+                let word = self.lines[self.active_line].get_word();
                 self.update_letter_pool(res);
-                if self.active_line < 5 {
-                    self.active_line += 1;
+                if self.engine.is_answer(&word) {
+                    self.notice = Some(format!(
+                        "Found {}, one word to go",
+                        word.to_ascii_uppercase()
+                    ));
+                }
+                if self.has_next_line() {
+                    self.state = BoardState::Guessed(self.result_grid(false));
+                    self.next_line();
                 } else {
-                    self.state = BoardState::Failed;
+                    self.show_lies();
+                    self.with_clock_mut(Clock::stop);
+                    self.state = BoardState::Failed(self.result_grid(false));
                 };
             }
             WordLineState::Invalid => {
@@ -137,19 +349,345 @@ impl Board {
         CmdResult::None
     }
 
+    // Start marking the entered word, or confirm the marked feedback
+    fn handle_feedback_submit(&mut self) -> CmdResult {
+        let line = self
+            .lines
+            .get_mut(self.active_line)
+            .expect("Could not get active word line");
+
+        if self.marking.is_none() {
+            if line.is_complete() && is_valid_word(&line.get_word()) {
+                line.set_letter_states(&[LetterState::Incorrect; WORD_LENGTH]);
+                line.show_letter_states();
+                line.set_selected(Some(0));
+                self.marking = Some(0);
+            } else if line.is_complete() {
+                self.handle_invalid_word();
+            }
+            return CmdResult::None;
+        }
+
+        let word = line.get_word();
+        let states = line.get_letter_states();
+        let constraints = self
+            .constraints
+            .clone()
+            .with_feedback(&word, states.clone());
+        let candidates = solver::candidates(&solver::answers(), &constraints);
+
+        // The player's word must be an answer, so the feedback has a mistake
+        if self.mode == BoardMode::Reverse && candidates.is_empty() {
+            self.notice = Some("No word matches these clues, check the colours".into());
+            return CmdResult::None;
+        }
+
+        line.set_selected(None);
+        self.marking = None;
+        self.constraints = constraints;
+        self.update_letter_pool(scoring::letter_summary(&word, &states));
+        self.update_hints(candidates);
+
+        if states.iter().all(|s| *s == LetterState::Correct) {
+            self.state = BoardState::Succeded(self.result_grid(true));
+        } else if self.has_next_line() {
+            self.state = BoardState::Guessed(self.result_grid(false));
+            self.next_line();
+            if self.mode == BoardMode::Reverse {
+                self.make_guess();
+            }
+        } else {
+            self.state = BoardState::Failed(self.result_grid(false));
+        }
+
+        CmdResult::None
+    }
+
+    fn with_clock_mut(&mut self, f: impl FnOnce(&mut Clock)) {
+        if let Some(clock) = &self.clock {
+            f(&mut clock.write().expect("Could not get write access to Clock."));
+        }
+    }
+
+    // End the game when the time limit runs out
+    fn check_time_limit(&mut self) {
+        let expired = self
+            .clock
+            .as_ref()
+            .is_some_and(|clock| clock.read().unwrap().is_expired());
+        if expired && matches!(self.state, BoardState::Playing | BoardState::Animating) {
+            self.show_lies();
+            self.with_clock_mut(Clock::stop);
+            self.state = BoardState::Failed(self.result_grid(false));
+        }
+    }
+
+    fn current_player(&self) -> Option<usize> {
+        self.players
+            .as_ref()
+            .map(|players| players.read().unwrap().turn)
+    }
+
+    // Name of the player next to their rows, or to the active row on their turn
+    fn render_label(&self, frame: &mut Frame, area: Rect, line: usize) {
+        let Some(players) = &self.players else {
+            return;
+        };
+        let players = players.read().unwrap();
+
+        let player = self.lines[line].player().or_else(|| {
+            let playing = matches!(self.state, BoardState::Playing | BoardState::Animating);
+            (line == self.active_line && playing).then_some(players.turn)
+        });
+        let Some(player) = player else {
+            return;
+        };
+
+        let width = LABEL_WIDTH.min(area.x);
+        if width < 2 {
+            return;
+        }
+        let rect = Rect {
+            x: area.x - width,
+            y: area.y + (self.cell_size.height() - 1) / 2,
+            width: width - 1,
+            height: 1,
+        };
+        let label = Paragraph::new(players.names[player].as_str())
+            .style(Style::default().fg(player_colour(player)).bold())
+            .alignment(Alignment::Right);
+        frame.render_widget(label, rect);
+    }
+
+    fn has_next_line(&self) -> bool {
+        self.max_guesses
+            .is_none_or(|max| self.active_line + 1 < max)
+    }
+
+    fn next_line(&mut self) {
+        self.active_line += 1;
+        if self.lines.len() <= self.active_line {
+            self.lines.push(WordLine::default());
+        }
+    }
+
+    // Enter the best guess on the active line, ready for the player's feedback
+    fn make_guess(&mut self) {
+        let guess = {
+            let hints = self
+                .hints
+                .read()
+                .expect("Could not get read access to Hints.");
+            hints
+                .suggestions
+                .first()
+                .or(hints.candidates.first())
+                .expect("Should have a guess while candidates remain")
+                .to_string()
+        };
+
+        let line = self
+            .lines
+            .get_mut(self.active_line)
+            .expect("Could not get active word line");
+        guess.chars().for_each(|ch| line.push_char(ch));
+        line.set_letter_states(&[LetterState::Incorrect; WORD_LENGTH]);
+        line.show_letter_states();
+        line.set_selected(Some(0));
+        self.marking = Some(0);
+    }
+
+    fn handle_mark_move(&mut self, right: bool) {
+        if let Some(idx) = self.marking {
+            let idx = if right {
+                (idx + 1).min(WORD_LENGTH - 1)
+            } else {
+                idx.saturating_sub(1)
+            };
+            self.marking = Some(idx);
+            self.lines[self.active_line].set_selected(Some(idx));
+        }
+    }
+
+    fn handle_mark_cycle(&mut self) {
+        if let Some(idx) = self.marking {
+            self.lines[self.active_line].cycle_letter_state(idx);
+        }
+    }
+
+    // Set the selected tile's colour directly and move on to the next
+    fn handle_mark_set(&mut self, state: LetterState) {
+        if let Some(idx) = self.marking {
+            let line = &mut self.lines[self.active_line];
+            let mut states = line.get_letter_states();
+            states[idx] = state;
+            line.set_letter_states(&states);
+            self.handle_mark_move(true);
+        }
+    }
+
+    // Select a clicked tile and cycle its colour
+    fn handle_mark_click(&mut self, column: u16, row: u16) {
+        let Some(idx) = self
+            .tiles
+            .iter()
+            .position(|rect| contains(rect, column, row))
+        else {
+            return;
+        };
+        if self.marking.is_some() {
+            self.marking = Some(idx);
+            self.lines[self.active_line].set_selected(Some(idx));
+            self.handle_mark_cycle();
+        }
+    }
+
+    // Key pressed by clicking the on-screen keyboard
+    fn clicked_key(&self, column: u16, row: u16) -> Option<Event<UserEvent>> {
+        let keys = self.keys.read().unwrap();
+        let (_, key) = keys.iter().find(|(rect, _)| contains(rect, column, row))?;
+        let ev = match key {
+            PoolKey::Letter(ch) => {
+                Event::Keyboard(KeyEvent::new(Key::Char(*ch), KeyModifiers::NONE))
+            }
+            PoolKey::Enter => Event::User(UserEvent::Action(Action::Submit)),
+            PoolKey::Delete => Event::User(UserEvent::Action(Action::Delete)),
+        };
+        Some(ev)
+    }
+
+    // Return to editing the word
+    fn handle_mark_cancel(&mut self) {
+        let line = &mut self.lines[self.active_line];
+        line.clear_letter_states();
+        line.set_selected(None);
+        self.marking = None;
+    }
+
+    // Suggests a word that fits the clues so far
+    fn handle_hint(&mut self) {
+        // Lies and per-player answers would make the clues misleading
+        if self.mode != BoardMode::Play
+            || self.engine.variant() != Variant::Classic
+            || self.players.is_some()
+        {
+            self.notice = Some("No hints in this mode".into());
+            return;
+        }
+
+        let constraints = self.lines[..self.active_line].iter().fold(
+            Constraints::default(),
+            |constraints, line| {
+                constraints.with_feedback(&line.get_word(), line.get_letter_states())
+            },
+        );
+        let candidates = solver::candidates(&solver::answers(), &constraints);
+        self.notice = Some(match solver::suggest(&candidates, 1).first() {
+            Some(word) if candidates.len() == 1 => {
+                format!("Only {} fits", word.to_ascii_uppercase())
+            }
+            Some(word) => format!(
+                "{} words fit, try {}",
+                candidates.len(),
+                word.to_ascii_uppercase()
+            ),
+            None => "No word in the answer list fits".into(),
+        });
+    }
+
+    fn update_hints(&mut self, candidates: Vec<&'static str>) {
+        let mut writer = self
+            .hints
+            .write()
+            .expect("Could not get write access to Hints.");
+        *writer = Hints::new(candidates);
+    }
+
+    fn result_grid(&self, solved: bool) -> ResultGrid {
+        // The active line isn't played if the time ran out while typing
+        let played: Vec<&WordLine> = self.lines[..=self.active_line]
+            .iter()
+            .filter(|line| line.is_scored())
+            .collect();
+        let guesses: Vec<String> = played.iter().map(|line| line.get_word()).collect();
+        let players: Vec<String> = match &self.players {
+            Some(players) => {
+                let players = players.read().unwrap();
+                played
+                    .iter()
+                    .filter_map(|line| line.player())
+                    .map(|player| players.names[player].clone())
+                    .collect()
+            }
+            None => vec![],
+        };
+
+        // Assist and reverse games only know the answer once it's guessed
+        let answer = match guesses.last() {
+            Some(word) if solved && self.mode != BoardMode::Play => word.clone(),
+            _ => self.engine.reveal(),
+        };
+
+        ResultGrid {
+            solution: Solution {
+                answer,
+                ..self.solution.clone()
+            },
+            variant: self.engine.variant(),
+            lies: self.engine.lies(),
+            max_guesses: self.max_guesses,
+            solved,
+            lines_used: played.len(),
+            guesses,
+            players,
+            winner: played
+                .last()
+                .and_then(|line| line.player())
+                .filter(|_| solved),
+            grid: played.iter().map(|line| line.get_letter_states()).collect(),
+            duration: self.started.elapsed(),
+            time: self
+                .clock
+                .as_ref()
+                .map(|clock| clock.read().unwrap().elapsed()),
+            splits: self
+                .clock
+                .as_ref()
+                .map(|clock| clock.read().unwrap().splits().to_vec())
+                .unwrap_or_default(),
+        }
+    }
+
     // Trigger shake animation
     fn handle_invalid_word(&mut self) {
         self.anim_last_frame_time = Instant::now();
         self.state = BoardState::Animating;
     }
 
+    // Mark the tiles that showed the wrong colour
+    fn show_lies(&mut self) {
+        for (line, lie) in self.lines.iter_mut().zip(self.engine.lies()) {
+            line.set_lie(lie);
+        }
+    }
+
     fn update_letter_pool(&mut self, map: IndexMap<char, LetterState>) {
+        // The pool stays neutral when feedback can't be trusted
+        // Race rows are scored against different answers
+        if self.engine.variant() == Variant::Fibble || self.engine.is_race() {
+            return;
+        }
+
         let mut writer = self
             .letter_states
             .write()
             .expect("Could not get write access to LetterStates.");
         for (ch, state) in &map {
-            writer.insert(*ch, *state);
+            // Never downgrade what's already known about a letter
+            let best = writer
+                .get(ch)
+                .map_or(*state, |current| (*current).max(*state));
+            writer.insert(*ch, best);
         }
     }
 
@@ -196,7 +734,9 @@ impl MockComponent for Board {
             }
         } else {
             // Animation finished - reset
-            self.state = BoardState::Playing;
+            if self.state == BoardState::Animating {
+                self.state = BoardState::Playing;
+            }
             self.anim_last_frame_index = 0;
             0
         };
@@ -207,18 +747,18 @@ impl MockComponent for Board {
                 frame.render_widget(block, frame.size())
             }
 
-            let rects = Layout::vertical([
-                Constraint::Length(CELL_HEIGHT + CELL_VER_MARGIN),
-                Constraint::Length(CELL_HEIGHT + CELL_VER_MARGIN),
-                Constraint::Length(CELL_HEIGHT + CELL_VER_MARGIN),
-                Constraint::Length(CELL_HEIGHT + CELL_VER_MARGIN),
-                Constraint::Length(CELL_HEIGHT + CELL_VER_MARGIN),
-                Constraint::Length(CELL_HEIGHT + CELL_VER_MARGIN),
-            ])
-            .split(area);
+            // Scroll older rows out of view to keep the active line visible
+            let size = self.cell_size;
+            let visible = (area.height / size.row_height()).max(1) as usize;
+            let first = (self.active_line + 1).saturating_sub(visible);
+            let rows = visible.min(self.lines.len() - first);
+
+            let rects =
+                Layout::vertical(vec![Constraint::Length(size.row_height()); rows]).split(area);
+            self.tiles.clear();
 
-            for i in 0..6 {
-                let mut area = rects[i];
+            for (row, i) in (first..first + rows).enumerate() {
+                let mut area = rects[row];
 
                 // Animate active line
                 if self.state == BoardState::Animating && i == self.active_line {
@@ -228,13 +768,28 @@ impl MockComponent for Board {
                     };
                 }
 
+                if i == self.active_line {
+                    let step = size.width() + size.hor_margin();
+                    self.tiles = (0..WORD_LENGTH as u16)
+                        .map(|j| Rect {
+                            x: area.x + j * step,
+                            width: size.width(),
+                            ..area
+                        })
+                        .collect();
+                }
+
                 if let Some(wl) = self.lines.get_mut(i) {
-                    wl.set_width(CELL_WIDTH);
-                    wl.set_margin(CELL_HOR_MARGIN);
-                    wl.set_letter_size(LETTER_SIZE);
+                    wl.set_editing(
+                        i == self.active_line && self.marking.is_none() && !wl.is_scored(),
+                    );
+                    wl.set_width(size.width());
+                    wl.set_margin(size.hor_margin());
+                    wl.set_letter_size(size.letter_size());
                     wl.set_window_bg(self.bg);
                     wl.view(frame, area);
                 }
+                self.render_label(frame, area, i);
             }
         }
     }
@@ -244,6 +799,9 @@ impl MockComponent for Board {
     }
 
     fn attr(&mut self, attr: Attribute, value: AttrValue) {
+        if let (Attribute::Custom(CELL_SIZE_ATTR), AttrValue::Number(n)) = (attr, &value) {
+            self.cell_size = CellSize::ALL.get(*n as usize).copied().unwrap_or_default();
+        }
         self.props.set(attr, value)
     }
 
@@ -261,53 +819,128 @@ impl MockComponent for Board {
     }
 }
 
-impl Component<Msg, NoUserEvent> for Board {
-    fn on(&mut self, ev: Event<NoUserEvent>) -> Option<Msg> {
+impl Component<Msg, UserEvent> for Board {
+    fn on(&mut self, ev: Event<UserEvent>) -> Option<Msg> {
+        let finished = self.state == BoardState::Finished;
+
         let _ = match ev {
+            Event::Tick => {
+                self.check_time_limit();
+                CmdResult::None
+            }
+
+            // Clicking a key works like pressing it
+            Event::User(UserEvent::Click { column, row }) => {
+                if let Some(ev) = self.clicked_key(column, row) {
+                    return self.on(ev);
+                }
+                self.handle_mark_click(column, row);
+                CmdResult::None
+            }
+
             // Background colour hotkeys
-            Event::Keyboard(KeyEvent {
-                code: Key::PageDown,
-                ..
-            }) => {
+            Event::User(UserEvent::Action(Action::BgNext)) => {
                 self.next_bg_colour();
                 CmdResult::None
             }
-            Event::Keyboard(KeyEvent {
-                code: Key::PageUp, ..
-            }) => {
+            Event::User(UserEvent::Action(Action::BgPrev)) => {
                 self.prev_bg_colour();
                 CmdResult::None
             }
-            Event::Keyboard(KeyEvent {
-                code: Key::Home, ..
-            }) => {
+            Event::User(UserEvent::Action(Action::BgReset)) => {
                 self.reset_bg_colour();
                 CmdResult::None
             }
 
-            // Input
+            // Copy result once the game is over, ignoring other input
             Event::Keyboard(KeyEvent {
-                code: Key::Enter, ..
-            }) => self.perform(Cmd::Submit),
+                code: Key::Char('c' | 'C'),
+                modifiers: KeyModifiers::NONE | KeyModifiers::SHIFT,
+            }) if finished => return Some(Msg::CopyResult),
+            Event::User(UserEvent::Action(Action::Submit)) if finished => {
+                return Some(Msg::NextGame)
+            }
+            Event::Keyboard(_) | Event::User(UserEvent::Action(_)) if finished => CmdResult::None,
+
+            Event::User(UserEvent::Action(Action::Hint)) => {
+                self.handle_hint();
+                CmdResult::None
+            }
+
+            // Feedback entry
+            Event::User(UserEvent::Action(Action::Left)) if self.marking.is_some() => {
+                self.handle_mark_move(false);
+                CmdResult::None
+            }
+            Event::User(UserEvent::Action(Action::Right)) if self.marking.is_some() => {
+                self.handle_mark_move(true);
+                CmdResult::None
+            }
+            Event::User(UserEvent::Action(Action::CycleColour)) if self.marking.is_some() => {
+                self.handle_mark_cycle();
+                CmdResult::None
+            }
             Event::Keyboard(KeyEvent {
-                code: Key::Backspace,
-                ..
-            }) => self.perform(Cmd::Delete),
+                code: Key::Char(ch),
+                modifiers: KeyModifiers::NONE | KeyModifiers::SHIFT,
+            }) if self.marking.is_some() => {
+                match ch.to_ascii_lowercase() {
+                    'g' => self.handle_mark_set(LetterState::Correct),
+                    'y' => self.handle_mark_set(LetterState::Contains),
+                    'x' | '.' => self.handle_mark_set(LetterState::Incorrect),
+                    _ => {}
+                }
+                CmdResult::None
+            }
+            Event::User(UserEvent::Action(
+                Action::Delete | Action::DeleteNext | Action::DeleteWord | Action::ClearLine,
+            )) if self.marking.is_some() => {
+                // The computer's guess can't be edited
+                if self.mode != BoardMode::Reverse {
+                    self.handle_mark_cancel();
+                }
+                CmdResult::None
+            }
+
+            // Input
+            Event::User(UserEvent::Action(Action::Submit)) => self.perform(Cmd::Submit),
+            Event::User(UserEvent::Action(Action::Delete)) => self.perform(Cmd::Delete),
+            Event::User(UserEvent::Action(
+                action @ (Action::DeleteNext
+                | Action::DeleteWord
+                | Action::ClearLine
+                | Action::Left
+                | Action::Right),
+            )) => self.handle_input_edit(action),
             Event::Keyboard(KeyEvent {
                 code: Key::Char(ch),
                 modifiers: KeyModifiers::NONE | KeyModifiers::SHIFT,
             }) => self.perform(Cmd::Type(ch)),
 
-
             _ => CmdResult::None,
         };
 
+        // A notice goes with the guess that caused it, and gives way to the end of the game
         match self.state.clone() {
+            BoardState::Guessed(rg) => {
+                self.state = BoardState::Playing;
+                Some(Msg::Guessed(rg, self.notice.take()))
+            }
             BoardState::Succeded(rg) => {
                 self.state = BoardState::Finished;
+                self.notice = None;
                 Some(Msg::Succeded(rg))
-            },
-            _ => Some(Msg::None)
+            }
+            BoardState::Failed(rg) => {
+                self.state = BoardState::Finished;
+                self.notice = None;
+                Some(Msg::Failed(rg))
+            }
+            _ => Some(self.notice.take().map_or(Msg::None, Msg::Status)),
         }
     }
 }
+
+fn contains(rect: &Rect, column: u16, row: u16) -> bool {
+    (rect.left()..rect.right()).contains(&column) && (rect.top()..rect.bottom()).contains(&row)
+}
@@ -0,0 +1,128 @@
+use std::{rc::Rc, sync::RwLock};
+
+use tuirealm::{
+    command::{Cmd, CmdResult},
+    props::Style,
+    tui::{
+        layout::Rect,
+        style::Stylize,
+        text::{Line, Span},
+        widgets::Paragraph,
+    },
+    AttrValue, Attribute, Component, Event, Frame, MockComponent, Props, State,
+};
+
+use crate::{
+    model::{Msg, UserEvent},
+    solver, theme,
+};
+
+const MAX_SUGGESTIONS: usize = 5;
+
+// Remaining answers and suggested guesses, shared with the board
+#[derive(Debug, Clone, PartialEq)]
+pub struct Hints {
+    pub candidates: Vec<&'static str>,
+    pub suggestions: Vec<&'static str>,
+}
+
+impl Hints {
+    pub fn new(candidates: Vec<&'static str>) -> Self {
+        let suggestions = solver::suggest(&candidates, MAX_SUGGESTIONS);
+
+        Self {
+            candidates,
+            suggestions,
+        }
+    }
+}
+
+impl Default for Hints {
+    fn default() -> Self {
+        Self::new(solver::answers())
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct Candidates {
+    props: Props,
+    hints: Rc<RwLock<Hints>>,
+}
+
+impl Candidates {
+    pub fn new() -> (Self, Rc<RwLock<Hints>>) {
+        let hints = Rc::new(RwLock::new(Hints::default()));
+        let hints_rc = Rc::clone(&hints);
+
+        let candidates = Self {
+            props: Default::default(),
+            hints,
+        };
+
+        (candidates, hints_rc)
+    }
+}
+
+impl MockComponent for Candidates {
+    fn view(&mut self, frame: &mut Frame, area: Rect) {
+        if self.props.get_or(Attribute::Display, AttrValue::Flag(true)) == AttrValue::Flag(true) {
+            let guard = self.hints.read().unwrap();
+            let heading = Style::default().fg(theme::LETTER_FG).bold();
+            let word = Style::default().fg(theme::STATUS_FG);
+
+            let mut lines = vec![Line::from(Span::styled("Suggestions", heading))];
+            if guard.suggestions.is_empty() {
+                lines.push(Line::from(Span::styled("-", word)));
+            }
+            for guess in &guard.suggestions {
+                lines.push(Line::from(Span::styled(guess.to_ascii_uppercase(), word)));
+            }
+
+            lines.push(Line::default());
+            lines.push(Line::from(Span::styled(
+                format!("Candidates ({})", guard.candidates.len()),
+                heading,
+            )));
+            if guard.candidates.is_empty() {
+                lines.push(Line::from(Span::styled("No words match", word)));
+            }
+
+            // Fill remaining space with as many candidates as will fit
+            let room = (area.height as usize).saturating_sub(lines.len());
+            for (i, candidate) in guard.candidates.iter().take(room).enumerate() {
+                if i + 1 == room && guard.candidates.len() > room {
+                    lines.push(Line::from(Span::styled("...", word)));
+                } else {
+                    lines.push(Line::from(Span::styled(
+                        candidate.to_ascii_uppercase(),
+                        word,
+                    )));
+                }
+            }
+
+            frame.render_widget(Paragraph::new(lines), area);
+        }
+    }
+
+    fn query(&self, attr: Attribute) -> Option<AttrValue> {
+        self.props.get(attr)
+    }
+
+    fn attr(&mut self, attr: Attribute, value: AttrValue) {
+        self.props.set(attr, value)
+    }
+
+    fn state(&self) -> State {
+        State::None
+    }
+
+    fn perform(&mut self, _: Cmd) -> CmdResult {
+        CmdResult::None
+    }
+}
+
+impl Component<Msg, UserEvent> for Candidates {
+    fn on(&mut self, _: Event<UserEvent>) -> Option<Msg> {
+        None
+    }
+}
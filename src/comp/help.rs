@@ -0,0 +1,101 @@
+use tuirealm::{
+    command::{Cmd, CmdResult},
+    props::Style,
+    tui::{
+        layout::Rect,
+        style::Stylize,
+        text::{Line, Span},
+        widgets::{Block, BorderType, Borders, Clear, Padding, Paragraph},
+    },
+    AttrValue, Attribute, Component, Event, Frame, MockComponent, Props, State,
+};
+
+use crate::{
+    keymap::{Action, Keymap},
+    model::{Msg, UserEvent},
+    theme,
+};
+
+const KEYS_WIDTH: usize = 22;
+
+// List of actions and their keys, drawn over the board
+#[derive(Debug, Clone)]
+pub struct Help {
+    props: Props,
+    keymap: Keymap,
+}
+
+impl Help {
+    pub fn new(keymap: Keymap) -> Self {
+        Self {
+            props: Props::default(),
+            keymap,
+        }
+    }
+
+    // Room needed including the border
+    pub fn size(&self) -> (u16, u16) {
+        let width = self
+            .lines()
+            .iter()
+            .map(|line| line.width())
+            .max()
+            .unwrap_or_default();
+        (width as u16 + 4, self.lines().len() as u16 + 2)
+    }
+
+    fn lines(&self) -> Vec<Line<'static>> {
+        let keys = Style::default().fg(theme::LETTER_FG).bold();
+        let description = Style::default().fg(theme::STATUS_FG);
+
+        let mut lines = vec![Line::from(Span::styled(
+            format!("{:<KEYS_WIDTH$}Type letters to guess", "a-z"),
+            description,
+        ))];
+        lines.extend(Action::ALL.into_iter().map(|action| {
+            Line::from(vec![
+                Span::styled(format!("{:<KEYS_WIDTH$}", self.keymap.keys(action)), keys),
+                Span::styled(action.description(), description),
+            ])
+        }));
+        lines
+    }
+}
+
+impl MockComponent for Help {
+    fn view(&mut self, frame: &mut Frame, area: Rect) {
+        if self.props.get_or(Attribute::Display, AttrValue::Flag(true)) == AttrValue::Flag(true) {
+            let block = Block::default()
+                .title(" Keys ")
+                .borders(Borders::ALL)
+                .border_type(BorderType::Rounded)
+                .border_style(Style::default().fg(theme::STATUS_FG))
+                .padding(Padding::horizontal(1));
+
+            frame.render_widget(Clear, area);
+            frame.render_widget(Paragraph::new(self.lines()).block(block), area);
+        }
+    }
+
+    fn query(&self, attr: Attribute) -> Option<AttrValue> {
+        self.props.get(attr)
+    }
+
+    fn attr(&mut self, attr: Attribute, value: AttrValue) {
+        self.props.set(attr, value)
+    }
+
+    fn state(&self) -> State {
+        State::None
+    }
+
+    fn perform(&mut self, _: Cmd) -> CmdResult {
+        CmdResult::None
+    }
+}
+
+impl Component<Msg, UserEvent> for Help {
+    fn on(&mut self, _: Event<UserEvent>) -> Option<Msg> {
+        None
+    }
+}
@@ -3,7 +3,6 @@ use std::{env, rc::Rc, sync::RwLock};
 use indexmap::IndexMap;
 use tuirealm::{
     command::{Cmd, CmdResult},
-    event::{Key, KeyEvent},
     props::{Alignment, Style},
     tui::{
         layout::Rect,
@@ -11,24 +10,44 @@ use tuirealm::{
         text::{Line, Span},
         widgets::Paragraph,
     },
-    AttrValue, Attribute, Component, Event, Frame, MockComponent, NoUserEvent, Props, State,
+    AttrValue, Attribute, Component, Event, Frame, MockComponent, Props, State,
 };
 
 use crate::{
-    model::{LetterState, Msg},
+    keymap::Action,
+    layouts::{KeyboardLayout, MAX_ROWS as POOL_ROWS},
+    model::{LetterState, Msg, UserEvent},
     theme,
 };
 
+// Key on the on-screen keyboard
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PoolKey {
+    Letter(char),
+    Enter,
+    Delete,
+}
+
+// Where each key was last drawn, so the board can tell which one was clicked
+pub type KeyAreas = Rc<RwLock<Vec<(Rect, PoolKey)>>>;
+
 #[derive(Debug, Clone, Default)]
 pub struct LetterPool {
     props: Props,
     pool: Rc<RwLock<IndexMap<char, LetterState>>>,
-    qwerty_mode: bool,
+    keys: KeyAreas,
+    layouts: Vec<KeyboardLayout>, // Tab cycles through these
+    layout: usize,
 }
 
 impl LetterPool {
-    pub fn new() -> (Self, Rc<RwLock<IndexMap<char, LetterState>>>) {
+    pub fn new(layouts: Vec<KeyboardLayout>) -> (Self, Rc<RwLock<IndexMap<char, LetterState>>>) {
         let qwerty_mode = matches!(env::var("TURDLE_QWERTY_MODE"), Ok(s) if s == "1");
+        let layout = if qwerty_mode {
+            layouts.iter().position(KeyboardLayout::is_qwerty)
+        } else {
+            None
+        };
 
         let mut pool = IndexMap::with_capacity(26);
         ('a'..='z').for_each(|c| _ = pool.insert(c, LetterState::Unused));
@@ -39,78 +58,95 @@ impl LetterPool {
         let letter_pool = Self {
             props: Default::default(),
             pool,
-            qwerty_mode,
+            keys: Default::default(),
+            layouts,
+            layout: layout.unwrap_or_default(),
         };
 
         (letter_pool, pool_rc)
     }
 
-    pub fn toggle_qwerty_mode(&mut self) {
-        self.qwerty_mode = !self.qwerty_mode;
+    pub fn key_areas(&self) -> KeyAreas {
+        Rc::clone(&self.keys)
+    }
+
+    pub fn next_layout(&mut self) {
+        self.layout = (self.layout + 1) % self.layouts.len().max(1);
     }
 }
 
 impl MockComponent for LetterPool {
     fn view(&mut self, frame: &mut Frame, area: Rect) {
         if self.props.get_or(Attribute::Display, AttrValue::Flag(true)) == AttrValue::Flag(true) {
-            // Setup templates to draw letters in alpha/QWERTY order
-            let row_ordering_template = if self.qwerty_mode {
-                vec![
-                    vec!['q', 'w', 'e', 'r', 't', 'y', 'u', 'i', 'o', 'p'],
-                    vec!['a', 's', 'd', 'f', 'g', 'h', 'j', 'k', 'l'],
-                    vec!['z', 'x', 'c', 'v', 'b', 'n', 'm'],
-                ]
-            } else {
-                vec![
-                    ('a'..'n').collect(),
-                    ('n'..='z').collect(),
-                    vec![], // Empty third line if alphabetical
-                ]
-            };
-
-            // Rows to be populated
-            let mut rows: Vec<Vec<Span>> = vec![vec![], vec![], vec![]];
+            // Letters of the current layout, with Enter and Delete around the bottom row
+            let layout = self
+                .layouts
+                .get(self.layout)
+                .map_or(&[][..], |l| &l.rows[..]);
+            let mut row_ordering_template: Vec<Vec<PoolKey>> = (0..POOL_ROWS)
+                .map(|i| {
+                    let row = layout.get(i).map_or(&[][..], |row| &row[..]);
+                    row.iter().copied().map(PoolKey::Letter).collect()
+                })
+                .collect();
+            let last = &mut row_ordering_template[POOL_ROWS - 1];
+            last.insert(0, PoolKey::Enter);
+            last.push(PoolKey::Delete);
+
+            // Rows to be populated, with the column and width of each key
+            let mut rows: Vec<Vec<Span>> = vec![vec![]; POOL_ROWS];
+            let mut key_cols: Vec<Vec<(u16, u16, PoolKey)>> = vec![vec![]; POOL_ROWS];
 
             // Populate rows
             let guard = self.pool.read().unwrap();
             for (i, row) in row_ordering_template.iter().enumerate() {
-                for ch in row {
-                    let fg;
-                    let bg;
-                    if let Some(state) = guard.get(ch) {
-                        fg = if *state == LetterState::Incorrect {
-                            theme::LETTER_FG_INCORRECT
-                        } else {
-                            theme::LETTER_FG
-                        };
-
-                        bg = match state {
-                            LetterState::Incorrect => theme::LETTER_BG_INCORRECT,
-                            LetterState::Contains => theme::LETTER_BG_CONTAINS,
-                            LetterState::Correct => theme::LETTER_BG_CORRECT,
-                            _ => theme::LETTER_BG_UNUSED,
-                        };
-
-                        let ch = ch.to_ascii_uppercase().to_string();
-                        if let Some(r) = rows.get_mut(i) {
-                            r.push(Span::styled("▐", Style::default().fg(bg)));
-                            r.push(Span::styled(ch, Style::default().bg(bg).fg(fg).bold()));
-                            r.push(Span::styled("▌", Style::default().fg(bg)));
-                        }
-                    }
+                let mut col = 0;
+                for key in row {
+                    let (label, state) = match key {
+                        PoolKey::Letter(ch) => (
+                            ch.to_ascii_uppercase().to_string(),
+                            guard.get(ch).copied().unwrap_or_default(),
+                        ),
+                        PoolKey::Enter => ("ENTER".to_string(), LetterState::Unused),
+                        PoolKey::Delete => ("⌫".to_string(), LetterState::Unused),
+                    };
+
+                    let fg = if state == LetterState::Incorrect {
+                        theme::LETTER_FG_INCORRECT
+                    } else {
+                        theme::LETTER_FG
+                    };
+
+                    let bg = match state {
+                        LetterState::Incorrect => theme::LETTER_BG_INCORRECT,
+                        LetterState::Contains => theme::LETTER_BG_CONTAINS,
+                        LetterState::Correct => theme::LETTER_BG_CORRECT,
+                        _ => theme::LETTER_BG_UNUSED,
+                    };
+
+                    let width = label.chars().count() as u16 + 2;
+                    key_cols[i].push((col, width, *key));
+                    col += width;
+
+                    rows[i].push(Span::styled("▐", Style::default().fg(bg)));
+                    rows[i].push(Span::styled(label, Style::default().bg(bg).fg(fg).bold()));
+                    rows[i].push(Span::styled("▌", Style::default().fg(bg)));
                 }
             }
 
             // Row drawing logic
+            let mut keys = self.keys.write().unwrap();
+            keys.clear();
             for (i, row) in rows.into_iter().enumerate() {
                 let line = Line::from(row);
+                let line_width = line.width() as u16;
                 let par = Paragraph::new(line).alignment(Alignment::Center);
 
-                // Offset third row left in QWERTY mode
-                // Offset second row right in alpha mode
-                let offset: i16 = if self.qwerty_mode && i == 2 {
-                    -1
-                } else if !self.qwerty_mode && i == 1 {
+                // Offset a row right if it's as long as the one above, to stagger the keys
+                let above = i
+                    .checked_sub(1)
+                    .map(|above| row_ordering_template[above].len());
+                let offset: i16 = if above == Some(row_ordering_template[i].len()) {
                     1
                 } else {
                     0
@@ -123,6 +159,18 @@ impl MockComponent for LetterPool {
 
                 // Render row
                 frame.render_widget(par, area);
+
+                // Same centring as the paragraph
+                let start = area.x + area.width.saturating_sub(line_width) / 2;
+                for (col, width, key) in &key_cols[i] {
+                    let rect = Rect {
+                        x: start + col,
+                        y: area.y,
+                        width: *width,
+                        height: 1,
+                    };
+                    keys.push((rect.intersection(area), *key));
+                }
             }
         }
     }
@@ -144,11 +192,11 @@ impl MockComponent for LetterPool {
     }
 }
 
-impl Component<Msg, NoUserEvent> for LetterPool {
-    fn on(&mut self, ev: Event<NoUserEvent>) -> Option<Msg> {
+impl Component<Msg, UserEvent> for LetterPool {
+    fn on(&mut self, ev: Event<UserEvent>) -> Option<Msg> {
         let _ = match ev {
-            Event::Keyboard(KeyEvent { code: Key::Tab, .. }) => {
-                self.toggle_qwerty_mode();
+            Event::User(UserEvent::Action(Action::ToggleLayout)) => {
+                self.next_layout();
                 CmdResult::None
             }
             _ => CmdResult::None,
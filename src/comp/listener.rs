@@ -1,20 +1,17 @@
 use tui_realm_stdlib::Phantom as GlobalListener;
-use tuirealm::{
-    event::{Key, KeyEvent, KeyModifiers},
-    Component, Event, NoUserEvent,
-};
+use tuirealm::{Component, Event};
 
-use crate::model::Msg;
+use crate::{
+    keymap::Action,
+    model::{Msg, UserEvent},
+};
 
-impl Component<Msg, NoUserEvent> for GlobalListener {
-    fn on(&mut self, ev: tuirealm::Event<NoUserEvent>) -> Option<Msg> {
+impl Component<Msg, UserEvent> for GlobalListener {
+    fn on(&mut self, ev: tuirealm::Event<UserEvent>) -> Option<Msg> {
         match ev {
             // Global hotkeys
-            Event::Keyboard(KeyEvent { code: Key::Esc, .. })
-            | Event::Keyboard(KeyEvent {
-                code: Key::Char('c'),
-                modifiers: KeyModifiers::CONTROL,
-            }) => return Some(Msg::Quit),
+            Event::User(UserEvent::Action(Action::Quit)) => return Some(Msg::Quit),
+            Event::User(UserEvent::Action(Action::Help)) => return Some(Msg::ToggleHelp),
             _ => {}
         };
 
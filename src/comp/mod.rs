@@ -1,5 +1,11 @@
 pub mod big_letter;
 pub mod board;
+pub mod candidates;
+pub mod help;
 pub mod letter_pool;
 pub mod listener;
-pub mod word_line;
\ No newline at end of file
+pub mod opponents;
+pub mod players;
+pub mod status;
+pub mod timer;
+pub mod word_line;
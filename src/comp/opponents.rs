@@ -0,0 +1,121 @@
+use std::sync::{Arc, RwLock};
+
+use tuirealm::{
+    command::{Cmd, CmdResult},
+    props::Style,
+    tui::{
+        layout::Rect,
+        style::Stylize,
+        text::{Line, Span},
+        widgets::Paragraph,
+    },
+    AttrValue, Attribute, Component, Event, Frame, MockComponent, Props, State,
+};
+
+use crate::{
+    lan::Opponents,
+    model::{Msg, UserEvent},
+    theme,
+};
+
+pub const OPPONENTS_WIDTH: u16 = 14;
+
+// Small colour-only boards for the other players in a LAN race
+#[derive(Debug, Clone, Default)]
+pub struct OpponentBoards {
+    props: Props,
+    opponents: Arc<RwLock<Opponents>>,
+    max_guesses: Option<usize>,
+}
+
+impl OpponentBoards {
+    pub fn new(opponents: Arc<RwLock<Opponents>>, max_guesses: Option<usize>) -> Self {
+        Self {
+            props: Default::default(),
+            opponents,
+            max_guesses,
+        }
+    }
+}
+
+impl MockComponent for OpponentBoards {
+    fn view(&mut self, frame: &mut Frame, area: Rect) {
+        if self.props.get_or(Attribute::Display, AttrValue::Flag(true)) == AttrValue::Flag(true) {
+            let guard = self.opponents.read().unwrap();
+
+            let mut lines = vec![];
+            if !guard.connected {
+                lines.push(Line::from(Span::styled(
+                    "Disconnected",
+                    Style::default().fg(theme::TIMER_FG_LOW),
+                )));
+                lines.push(Line::default());
+            } else if guard.players.is_empty() {
+                lines.push(Line::from(Span::styled(
+                    "Alone so far",
+                    Style::default().fg(theme::STATUS_FG),
+                )));
+            }
+
+            for (name, opponent) in &guard.players {
+                let mark = match opponent {
+                    _ if opponent.left => " (left)",
+                    _ if opponent.solved => " ✓",
+                    _ if opponent.finished => " ✗",
+                    _ => "",
+                };
+                lines.push(Line::from(Span::styled(
+                    format!("{}{}", name, mark),
+                    Style::default().fg(theme::LETTER_FG).bold(),
+                )));
+
+                // Unplayed rows are shown empty, unless there's no limit
+                let rows = self
+                    .max_guesses
+                    .unwrap_or_default()
+                    .max(opponent.grid.len());
+                for i in 0..rows {
+                    let row = opponent.grid.get(i).map_or("     ", String::as_str);
+                    let spans: Vec<Span> = row
+                        .chars()
+                        .map(|ch| {
+                            let bg = match ch {
+                                'G' => theme::CELL_BG_CORRECT,
+                                'Y' => theme::CELL_BG_CONTAINS,
+                                '.' => theme::CELL_BG_INCORRECT,
+                                _ => theme::CELL_BG_EMPTY,
+                            };
+                            Span::styled("██ ", Style::default().fg(bg))
+                        })
+                        .collect();
+                    lines.push(Line::from(spans));
+                }
+                lines.push(Line::default());
+            }
+
+            frame.render_widget(Paragraph::new(lines), area);
+        }
+    }
+
+    fn query(&self, attr: Attribute) -> Option<AttrValue> {
+        self.props.get(attr)
+    }
+
+    fn attr(&mut self, attr: Attribute, value: AttrValue) {
+        self.props.set(attr, value)
+    }
+
+    fn state(&self) -> State {
+        State::None
+    }
+
+    fn perform(&mut self, _: Cmd) -> CmdResult {
+        CmdResult::None
+    }
+}
+
+impl Component<Msg, UserEvent> for OpponentBoards {
+    fn on(&mut self, _: Event<UserEvent>) -> Option<Msg> {
+        None
+    }
+}
@@ -0,0 +1,126 @@
+use std::{rc::Rc, sync::RwLock};
+
+use tuirealm::{
+    command::{Cmd, CmdResult},
+    props::{Alignment, Color, Style},
+    tui::{
+        layout::Rect,
+        style::Stylize,
+        text::{Line, Span},
+        widgets::Paragraph,
+    },
+    AttrValue, Attribute, Component, Event, Frame, MockComponent, Props, State,
+};
+
+use crate::{
+    model::{Msg, UserEvent},
+    theme,
+};
+
+pub const PLAYER_STYLES: [&str; 2] = ["turns", "race"];
+
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub enum PlayerStyle {
+    #[default]
+    Turns, // Players share one answer and take turns guessing it
+    Race, // Each player has their own answer, guessing on alternate rows
+}
+
+// Hot-seat players, shared between the model, board and turn indicator
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct Players {
+    pub names: Vec<String>,
+    pub scores: Vec<u32>,
+    pub turn: usize,
+    pub style: PlayerStyle,
+}
+
+impl Players {
+    pub fn new(names: Vec<String>, style: PlayerStyle) -> Self {
+        Self {
+            scores: vec![0; names.len()],
+            names,
+            turn: 0,
+            style,
+        }
+    }
+
+    pub fn current(&self) -> &str {
+        &self.names[self.turn]
+    }
+
+    pub fn next_turn(&mut self) {
+        self.turn = (self.turn + 1) % self.names.len();
+    }
+}
+
+pub fn player_colour(player: usize) -> Color {
+    theme::PLAYER_FG[player % theme::PLAYER_FG.len()]
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct TurnIndicator {
+    props: Props,
+    players: Rc<RwLock<Players>>,
+}
+
+impl TurnIndicator {
+    pub fn new(players: Rc<RwLock<Players>>) -> Self {
+        Self {
+            props: Default::default(),
+            players,
+        }
+    }
+}
+
+impl MockComponent for TurnIndicator {
+    fn view(&mut self, frame: &mut Frame, area: Rect) {
+        if self.props.get_or(Attribute::Display, AttrValue::Flag(true)) == AttrValue::Flag(true) {
+            let guard = self.players.read().unwrap();
+
+            let mut spans = vec![];
+            for (i, (name, score)) in guard.names.iter().zip(&guard.scores).enumerate() {
+                if i > 0 {
+                    spans.push(Span::styled("   ", Style::default()));
+                }
+
+                // Marker and bold name for the player whose turn it is
+                let style = Style::default().fg(player_colour(i));
+                if i == guard.turn {
+                    spans.push(Span::styled(format!("▶ {}", name), style.bold()));
+                } else {
+                    spans.push(Span::styled(format!("  {}", name), style));
+                }
+                spans.push(Span::styled(
+                    format!(" {}", score),
+                    Style::default().fg(theme::LETTER_FG).bold(),
+                ));
+            }
+
+            let par = Paragraph::new(Line::from(spans)).alignment(Alignment::Center);
+            frame.render_widget(par, area);
+        }
+    }
+
+    fn query(&self, attr: Attribute) -> Option<AttrValue> {
+        self.props.get(attr)
+    }
+
+    fn attr(&mut self, attr: Attribute, value: AttrValue) {
+        self.props.set(attr, value)
+    }
+
+    fn state(&self) -> State {
+        State::None
+    }
+
+    fn perform(&mut self, _: Cmd) -> CmdResult {
+        CmdResult::None
+    }
+}
+
+impl Component<Msg, UserEvent> for TurnIndicator {
+    fn on(&mut self, _: Event<UserEvent>) -> Option<Msg> {
+        None
+    }
+}
@@ -0,0 +1,10 @@
+use tui_realm_stdlib::Label as StatusLine;
+use tuirealm::Component;
+
+use crate::model::{Msg, UserEvent};
+
+impl Component<Msg, UserEvent> for StatusLine {
+    fn on(&mut self, _: tuirealm::Event<UserEvent>) -> Option<Msg> {
+        None
+    }
+}
@@ -0,0 +1,157 @@
+use std::{
+    rc::Rc,
+    sync::RwLock,
+    time::{Duration, Instant},
+};
+
+use tuirealm::{
+    command::{Cmd, CmdResult},
+    props::{Alignment, Style},
+    tui::{
+        layout::Rect,
+        style::Stylize,
+        text::{Line, Span},
+        widgets::Paragraph,
+    },
+    AttrValue, Attribute, Component, Event, Frame, MockComponent, Props, State,
+};
+
+use crate::{
+    model::{Msg, UserEvent},
+    theme,
+};
+
+const LOW_TIME: Duration = Duration::from_secs(10);
+
+// Game clock, started by the first keypress and shared with the board
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct Clock {
+    started: Option<Instant>,
+    stopped: Option<Duration>,
+    limit: Option<Duration>,
+    splits: Vec<Duration>, // Time of each submitted guess
+}
+
+impl Clock {
+    pub fn new(limit: Option<Duration>) -> Self {
+        Self {
+            limit,
+            ..Default::default()
+        }
+    }
+
+    pub fn start(&mut self) {
+        if self.started.is_none() {
+            self.started = Some(Instant::now());
+        }
+    }
+
+    pub fn stop(&mut self) {
+        if self.stopped.is_none() {
+            self.stopped = Some(self.elapsed());
+        }
+    }
+
+    pub fn split(&mut self) {
+        self.splits.push(self.elapsed());
+    }
+
+    pub fn elapsed(&self) -> Duration {
+        let elapsed = self
+            .stopped
+            .or(self.started.map(|started| started.elapsed()))
+            .unwrap_or_default();
+        self.limit.map_or(elapsed, |limit| elapsed.min(limit))
+    }
+
+    pub fn remaining(&self) -> Option<Duration> {
+        self.limit.map(|limit| limit.saturating_sub(self.elapsed()))
+    }
+
+    pub fn is_expired(&self) -> bool {
+        self.remaining() == Some(Duration::ZERO)
+    }
+
+    pub fn splits(&self) -> &[Duration] {
+        &self.splits
+    }
+}
+
+// E.g. "1:05.3"
+pub fn format_time(time: Duration) -> String {
+    let tenths = time.as_millis() / 100;
+    format!("{}:{:02}.{}", tenths / 600, tenths / 10 % 60, tenths % 10)
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct Timer {
+    props: Props,
+    clock: Rc<RwLock<Clock>>,
+}
+
+impl Timer {
+    pub fn new(clock: Rc<RwLock<Clock>>) -> Self {
+        Self {
+            props: Default::default(),
+            clock,
+        }
+    }
+}
+
+impl MockComponent for Timer {
+    fn view(&mut self, frame: &mut Frame, area: Rect) {
+        if self.props.get_or(Attribute::Display, AttrValue::Flag(true)) == AttrValue::Flag(true) {
+            let guard = self.clock.read().unwrap();
+
+            // Count down when there's a time limit
+            let (time, fg) = match guard.remaining() {
+                Some(left) if left <= LOW_TIME => (left, theme::TIMER_FG_LOW),
+                Some(left) => (left, theme::LETTER_FG),
+                None => (guard.elapsed(), theme::LETTER_FG),
+            };
+
+            let mut spans = vec![Span::styled(
+                format_time(time),
+                Style::default().fg(fg).bold(),
+            )];
+
+            // Time taken by the last guess
+            let splits = guard.splits();
+            if let Some(last) = splits.last() {
+                let prev = splits
+                    .len()
+                    .checked_sub(2)
+                    .map_or(Duration::ZERO, |i| splits[i]);
+                spans.push(Span::styled(
+                    format!("  +{}", format_time(*last - prev)),
+                    Style::default().fg(theme::STATUS_FG),
+                ));
+            }
+
+            let par = Paragraph::new(Line::from(spans)).alignment(Alignment::Center);
+            frame.render_widget(par, area);
+        }
+    }
+
+    fn query(&self, attr: Attribute) -> Option<AttrValue> {
+        self.props.get(attr)
+    }
+
+    fn attr(&mut self, attr: Attribute, value: AttrValue) {
+        self.props.set(attr, value)
+    }
+
+    fn state(&self) -> State {
+        State::None
+    }
+
+    fn perform(&mut self, _: Cmd) -> CmdResult {
+        CmdResult::None
+    }
+}
+
+impl Component<Msg, UserEvent> for Timer {
+    fn on(&mut self, _: Event<UserEvent>) -> Option<Msg> {
+        None
+    }
+}
@@ -9,8 +9,9 @@ use tuirealm::{
 };
 
 use crate::{
-    data::{answers::ANSWERS, words::WORDS},
+    engine::Engine,
     model::LetterState,
+    scoring::{self, WORD_LENGTH},
 };
 
 use super::big_letter::BigLetter;
@@ -22,14 +23,18 @@ pub struct WordLine {
     props: Props,
     state: WordLineState,
     letters: Vec<(char, LetterState)>,
-    big_letter_size: PixelSize,
+    big_letter_size: Option<PixelSize>, // None for plain letters
     cell_width: u16,
     cell_margin: u16,
     bg: Option<u8>,
-    answer: String,
     animating_reveal: bool,
     revealed: usize,
     last_frame_time: Instant,
+    selected: Option<usize>, // Tile being marked by the player
+    cursor: usize,           // Tile the next letter goes in, up to the number entered
+    editing: bool,           // Whether the cursor is shown
+    lie: Option<usize>,      // Tile shown with the wrong colour, marked once the game ends
+    player: Option<usize>,   // Hot-seat player who made the guess
 }
 
 #[derive(Debug, Default, Clone, PartialEq)]
@@ -42,12 +47,7 @@ pub enum WordLineState {
 }
 
 impl WordLine {
-    pub fn with_answer(mut self, answer: &str) -> Self {
-        self.answer = answer.to_string();
-        self
-    }
-
-    pub fn set_letter_size(&mut self, size: PixelSize) {
+    pub fn set_letter_size(&mut self, size: Option<PixelSize>) {
         self.big_letter_size = size;
     }
 
@@ -63,89 +63,145 @@ impl WordLine {
         self.bg = bg;
     }
 
+    // Replaces the letter at the cursor, or adds one at the end
     pub fn push_char(&mut self, ch: char) {
-        if self.letters.len() < 5 && ch.is_ascii_alphabetic() {
-            let ch = ch.to_ascii_lowercase();
+        if !ch.is_ascii_alphabetic() {
+            return;
+        }
+        let ch = ch.to_ascii_lowercase();
+        if let Some(letter) = self.letters.get_mut(self.cursor) {
+            *letter = (ch, LetterState::Entered);
+            self.cursor += 1;
+        } else if self.letters.len() < WORD_LENGTH {
             self.letters.push((ch, LetterState::Entered));
+            self.cursor = self.letters.len();
         }
     }
 
+    // Deletes the letter before the cursor
     pub fn del_char(&mut self) {
-        if !self.letters.is_empty() {
-            self.letters.pop();
+        if self.cursor > 0 {
+            self.cursor -= 1;
+            self.letters.remove(self.cursor);
         }
     }
 
+    // Deletes the letter at the cursor
+    pub fn del_next_char(&mut self) {
+        if self.cursor < self.letters.len() {
+            self.letters.remove(self.cursor);
+        }
+    }
+
+    // Deletes everything before the cursor, as the row holds a single word
+    pub fn del_word(&mut self) {
+        self.letters.drain(..self.cursor);
+        self.cursor = 0;
+    }
+
+    pub fn clear(&mut self) {
+        self.letters.clear();
+        self.cursor = 0;
+    }
+
+    pub fn move_cursor(&mut self, right: bool) {
+        self.cursor = if right {
+            (self.cursor + 1).min(self.letters.len())
+        } else {
+            self.cursor.saturating_sub(1)
+        };
+    }
+
+    pub fn set_editing(&mut self, editing: bool) {
+        self.editing = editing;
+    }
+
+    pub fn get_word(&self) -> String {
+        self.letters.iter().map(|(c, _)| c).collect()
+    }
+
     pub fn get_letter_states(&self) -> Vec<LetterState> {
         self.letters.iter().map(|(_, ls)| *ls).collect()
     }
 
-    // Assess entered word if 5 letters have been entered
-    pub fn submit(&mut self) -> WordLineState {
-        if self.letters.len() == 5 {
-            let word = self.letters.iter().map(|(c, _)| c).collect::<String>();
+    pub fn is_complete(&self) -> bool {
+        self.letters.len() == WORD_LENGTH
+    }
 
-            if word == self.answer {
-                let res = self.validate_letters();
-                self.begin_reveal();
-                self.state = WordLineState::Correct(res)
-            } else if ANSWERS.contains(word.as_str()) || WORDS.contains(word.as_str()) {
-                let res = self.validate_letters();
-                self.begin_reveal();
-                self.state = WordLineState::Incorrect(res)
-            } else {
-                return WordLineState::Invalid;
-            }
+    // True once the word has been given feedback
+    pub fn is_scored(&self) -> bool {
+        self.letters
+            .iter()
+            .any(|(_, ls)| *ls != LetterState::Entered)
+    }
+
+    // Feedback entered by the player rather than scored against the answer
+    pub fn set_letter_states(&mut self, states: &[LetterState]) {
+        for (letter, state) in self.letters.iter_mut().zip(states) {
+            letter.1 = *state;
         }
+    }
 
-        self.state.clone()
+    // Cycles a tile through incorrect, contains and correct
+    pub fn cycle_letter_state(&mut self, idx: usize) {
+        if let Some((_, state)) = self.letters.get_mut(idx) {
+            *state = match state {
+                LetterState::Incorrect => LetterState::Contains,
+                LetterState::Contains => LetterState::Correct,
+                _ => LetterState::Incorrect,
+            };
+        }
+    }
+
+    // Show tile colours immediately, without the reveal animation
+    pub fn show_letter_states(&mut self) {
+        self.animating_reveal = true;
+        self.revealed = WORD_LENGTH;
+    }
+
+    pub fn clear_letter_states(&mut self) {
+        self.set_letter_states(&[LetterState::Entered; WORD_LENGTH]);
+        self.animating_reveal = false;
+        self.revealed = 0;
+    }
+
+    pub fn set_selected(&mut self, idx: Option<usize>) {
+        self.selected = idx;
+    }
+
+    pub fn set_lie(&mut self, idx: Option<usize>) {
+        self.lie = idx;
+    }
+
+    pub fn set_player(&mut self, player: Option<usize>) {
+        self.player = player;
     }
 
-    fn validate_letters(&mut self) -> IndexMap<char, LetterState> {
-        // Helper function to remove `char`s from a `Vec<char>`.
-        fn remove_char(chars: &mut Vec<char>, char_to_remove: &char) {
-            if let Some(idx) = chars.iter().position(|c| c == char_to_remove) {
-                chars.swap_remove(idx);
+    pub fn player(&self) -> Option<usize> {
+        self.player
+    }
+
+    // Assess entered word if 5 letters have been entered
+    pub fn submit(&mut self, engine: &mut Engine) -> WordLineState {
+        if self.letters.len() == WORD_LENGTH {
+            let word = self.get_word();
+            if !engine.is_valid(&word) {
+                return WordLineState::Invalid;
             }
-        }
 
-        let answer_chars = self.answer.chars().collect::<Vec<_>>();
-        let mut answer_chars_not_matched = answer_chars.clone();
-        let mut res = IndexMap::new();
-
-        for i in 0..5 {
-            let (entered_char, _) = self
-                .letters
-                .get(i)
-                .expect("Entered letters did not have expected number of characters");
-            let answer_char = answer_chars
-                .get(i)
-                .expect("Answer did not have expected number of characters");
-
-            if entered_char == answer_char {
-                remove_char(&mut answer_chars_not_matched, entered_char);
-                res.insert(*entered_char, LetterState::Correct);
-                self.letters[i] = (*entered_char, LetterState::Correct);
-            } else if answer_chars_not_matched.contains(entered_char) {
-                remove_char(&mut answer_chars_not_matched, entered_char);
-
-                let current_state = res.insert(*entered_char, LetterState::Contains);
-                // Don't downgrade `Correct` letters to `Contains`
-                if current_state == Some(LetterState::Correct) {
-                    res.insert(*entered_char, LetterState::Correct);
-                }
+            let states = engine.score(&word);
+            self.set_letter_states(&states);
+            self.begin_reveal();
 
-                self.letters[i] = (*entered_char, LetterState::Contains);
+            let res = scoring::letter_summary(&word, &states);
+            if engine.is_solved(&word) {
+                self.state = WordLineState::Correct(res)
             } else {
-                let current_state = res.insert(*entered_char, LetterState::Incorrect);
-                // Don't downgrade `Contains` letters to `Incorrect`
-                if current_state == Some(LetterState::Contains) {
-                    res.insert(*entered_char, LetterState::Contains);
-                }
-                self.letters[i] = (*entered_char, LetterState::Incorrect);
+                self.state = WordLineState::Incorrect(res)
             }
         }
-        res
+
+        self.state.clone()
     }
 
     fn begin_reveal(&mut self) {
@@ -164,10 +220,14 @@ impl Default for WordLine {
             cell_width: Default::default(),
             cell_margin: Default::default(),
             bg: Default::default(),
-            answer: Default::default(),
             animating_reveal: Default::default(),
             revealed: Default::default(),
             last_frame_time: Instant::now(),
+            selected: Default::default(),
+            cursor: Default::default(),
+            editing: Default::default(),
+            lie: Default::default(),
+            player: Default::default(),
         }
     }
 }
@@ -175,7 +235,7 @@ impl Default for WordLine {
 impl MockComponent for WordLine {
     fn view(&mut self, frame: &mut Frame, area: Rect) {
         if self.props.get_or(Attribute::Display, AttrValue::Flag(true)) == AttrValue::Flag(true) {
-            let margin = 1;
+            let margin = self.cell_margin;
 
             // Outer cells with right-hand margin
             let col_rects = Layout::horizontal([
@@ -187,6 +247,9 @@ impl MockComponent for WordLine {
             ])
             .split(area);
 
+            // Cursor is hidden once the row is full and it's past the end
+            let cursor = self.editing.then_some(self.cursor);
+
             // Inner cells
             for i in 0..5 {
                 let cell_rect = Layout::horizontal([Constraint::Length(self.cell_width)])
@@ -203,6 +266,8 @@ impl MockComponent for WordLine {
                         let mut bl = BigLetter::default()
                             .with_char(Some(*ch))
                             .with_state(*state)
+                            .with_selected(self.selected == Some(i))
+                            .with_lie(self.lie == Some(i))
                             .with_size(self.big_letter_size)
                             .with_window_bg(self.bg)
                             .with_colour();
@@ -210,6 +275,7 @@ impl MockComponent for WordLine {
                     } else {
                         let mut bl = BigLetter::default()
                             .with_char(Some(*ch))
+                            .with_cursor(cursor == Some(i))
                             .with_size(self.big_letter_size)
                             .with_window_bg(self.bg)
                             .with_colour();
@@ -219,6 +285,7 @@ impl MockComponent for WordLine {
                     // Empty cells
                     BigLetter::default()
                         .with_char(None)
+                        .with_cursor(cursor == Some(i))
                         .with_size(self.big_letter_size)
                         .with_window_bg(self.bg)
                         .view(frame, cell_rect);
@@ -0,0 +1,132 @@
+use std::{env, fs, path::PathBuf};
+
+use anyhow::{bail, Context, Result};
+use serde_json::Value;
+
+use crate::{
+    keymap::{Action, Keymap},
+    layouts::{self, KeyboardLayout},
+};
+
+const CONFIG_FILE: &str = "config.json";
+
+// Settings from the config file, e.g.
+// {"layouts": ["azerty", "mine"], "custom_layouts": {"mine": ["qwfpb", "arstg", ...]},
+//  "keys": {"clear_line": ["Ctrl-W", "Ctrl-U"], "hint": "F3"}}
+#[derive(Debug, Clone)]
+pub struct Config {
+    pub layouts: Vec<KeyboardLayout>, // Keyboard layouts Tab cycles through, starting with the first
+    pub keymap: Keymap,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            layouts: layouts::defaults(),
+            keymap: Keymap::default(),
+        }
+    }
+}
+
+// Config file location, overridable with `TURDLE_CONFIG`
+pub fn path() -> Result<PathBuf> {
+    if let Some(path) = env::var_os("TURDLE_CONFIG") {
+        return Ok(PathBuf::from(path));
+    }
+
+    let dir = dirs::config_dir().context("Could not find a config directory")?;
+    Ok(dir.join(crate::APP_NAME).join(CONFIG_FILE))
+}
+
+// Defaults are used if there's no config file
+pub fn load() -> Result<Config> {
+    let path = path()?;
+    if !path.exists() {
+        return Ok(Config::default());
+    }
+
+    let text =
+        fs::read_to_string(&path).with_context(|| format!("Could not read {}", path.display()))?;
+    let json: Value = serde_json::from_str(&text)
+        .with_context(|| format!("Invalid JSON in {}", path.display()))?;
+    parse(&json).with_context(|| format!("Invalid config in {}", path.display()))
+}
+
+fn parse(json: &Value) -> Result<Config> {
+    let mut config = Config::default();
+
+    let mut custom = vec![];
+    if let Some(layouts) = json.get("custom_layouts") {
+        let layouts = layouts
+            .as_object()
+            .context("custom_layouts should map names to lists of rows")?;
+        for (name, rows) in layouts {
+            let rows: Vec<String> = rows
+                .as_array()
+                .and_then(|rows| {
+                    rows.iter()
+                        .map(|row| row.as_str().map(String::from))
+                        .collect()
+                })
+                .with_context(|| format!("Layout {} should be a list of rows", name))?;
+            custom.push(KeyboardLayout::custom(name, &rows)?);
+        }
+    }
+
+    match json.get("layouts") {
+        Some(names) => {
+            let names = names
+                .as_array()
+                .context("layouts should be a list of layout names")?;
+            config.layouts = names
+                .iter()
+                .map(|name| {
+                    let name = name.as_str().context("Layout names should be strings")?;
+                    custom
+                        .iter()
+                        .find(|layout| layout.name == name)
+                        .cloned()
+                        .or_else(|| layouts::builtin(name))
+                        .with_context(|| {
+                            format!(
+                                "Unknown layout {}, expected one of {} or a custom layout",
+                                name,
+                                layouts::names().join(", ")
+                            )
+                        })
+                })
+                .collect::<Result<_>>()?;
+            if config.layouts.is_empty() {
+                bail!("layouts should have at least one layout");
+            }
+        }
+        // Custom layouts are enabled after the defaults unless chosen explicitly
+        None => config.layouts.extend(custom),
+    }
+
+    // Only the actions listed are rebound
+    if let Some(keys) = json.get("keys") {
+        let keys = keys
+            .as_object()
+            .context("keys should map actions to a key or list of keys")?;
+        for (name, keys) in keys {
+            let action: Action = name.parse()?;
+            let keys: Vec<String> = match keys {
+                Value::String(key) => vec![key.clone()],
+                keys => keys
+                    .as_array()
+                    .and_then(|keys| {
+                        keys.iter()
+                            .map(|key| key.as_str().map(String::from))
+                            .collect()
+                    })
+                    .with_context(|| {
+                        format!("Keys for {} should be a key or list of keys", name)
+                    })?,
+            };
+            config.keymap.bind(action, &keys)?;
+        }
+    }
+
+    Ok(config)
+}
@@ -0,0 +1,344 @@
+use std::str::FromStr;
+
+use anyhow::{bail, Error};
+use indexmap::IndexMap;
+use rand::{seq::SliceRandom, Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+
+use crate::{
+    model::LetterState,
+    provider::Solution,
+    scoring::{self, WORD_LENGTH},
+    solver,
+};
+
+pub const VARIANTS: [&str; 4] = ["classic", "absurdle", "fibble", "xordle"];
+
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub enum Variant {
+    #[default]
+    Classic,
+    Absurdle, // No answer is picked up front and feedback is as unhelpful as possible
+    Fibble,   // One tile in every row shows the wrong colour
+    Xordle,   // Two answers with no letters in common
+}
+
+impl FromStr for Variant {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "classic" => Ok(Self::Classic),
+            "absurdle" => Ok(Self::Absurdle),
+            "fibble" => Ok(Self::Fibble),
+            "xordle" => Ok(Self::Xordle),
+            _ => bail!("Unknown variant \"{}\"", s),
+        }
+    }
+}
+
+// Provides the feedback for each guess
+#[derive(Debug, Clone, PartialEq)]
+pub enum Engine {
+    Fixed(String),
+    Absurdle(Vec<&'static str>), // Answers consistent with the feedback so far
+    Fibble {
+        answer: String,
+        lies: Vec<Option<usize>>, // Position of the lying tile in each scored row
+        rng: Box<ChaCha8Rng>,
+    },
+    Xordle {
+        answers: [String; 2],
+        found: [bool; 2],
+    },
+    Race {
+        engines: Vec<Engine>, // One per player, each with its own answer
+        player: usize,        // Player making the next guess
+        order: Vec<usize>,    // Player who made each scored guess
+    },
+}
+
+impl Engine {
+    // The seed makes the same choices for players sharing a puzzle
+    pub fn new(variant: Variant, solution: &Solution, seed: Option<u64>) -> Self {
+        let mut rng = seed.map_or_else(ChaCha8Rng::from_entropy, ChaCha8Rng::seed_from_u64);
+
+        match variant {
+            Variant::Classic => Self::Fixed(solution.answer.clone()),
+            Variant::Absurdle => Self::Absurdle(solver::answers()),
+            Variant::Fibble => Self::Fibble {
+                answer: solution.answer.clone(),
+                lies: vec![],
+                rng: Box::new(rng),
+            },
+            Variant::Xordle => Self::Xordle {
+                answers: [
+                    solution.answer.clone(),
+                    answer_pair(&solution.answer, &mut rng),
+                ],
+                found: [false; 2],
+            },
+        }
+    }
+
+    // Hot-seat race where each player guesses their own answer, the first being the solution's
+    pub fn race(variant: Variant, solution: &Solution, seed: Option<u64>, players: usize) -> Self {
+        let mut rng = seed.map_or_else(ChaCha8Rng::from_entropy, ChaCha8Rng::seed_from_u64);
+        let answers = solver::answers();
+
+        let mut picked = vec![solution.answer.clone()];
+        while picked.len() < players {
+            let answer = answers.choose(&mut rng).expect("Should have answers");
+            if !picked.iter().any(|a| a == answer) {
+                picked.push(answer.to_string());
+            }
+        }
+
+        let engines = picked
+            .into_iter()
+            .enumerate()
+            .map(|(i, answer)| {
+                let solution = Solution {
+                    answer,
+                    ..solution.clone()
+                };
+                Self::new(variant, &solution, seed.map(|s| s.wrapping_add(i as u64)))
+            })
+            .collect();
+
+        Self::Race {
+            engines,
+            player: 0,
+            order: vec![],
+        }
+    }
+
+    // Selects whose answer the next guess is scored against in a race
+    pub fn set_player(&mut self, next: usize) {
+        if let Self::Race {
+            engines, player, ..
+        } = self
+        {
+            *player = next.min(engines.len() - 1);
+        }
+    }
+
+    pub fn is_race(&self) -> bool {
+        matches!(self, Self::Race { .. })
+    }
+
+    pub fn variant(&self) -> Variant {
+        match self {
+            Self::Fixed(_) => Variant::Classic,
+            Self::Absurdle(_) => Variant::Absurdle,
+            Self::Fibble { .. } => Variant::Fibble,
+            Self::Xordle { .. } => Variant::Xordle,
+            Self::Race { engines, .. } => engines[0].variant(),
+        }
+    }
+
+    // True if the word is accepted as a guess
+    pub fn is_valid(&self, word: &str) -> bool {
+        match self {
+            // The answer may not be in the word lists, e.g. newer NYT answers
+            Self::Fixed(answer) | Self::Fibble { answer, .. } => {
+                word == answer || scoring::is_valid_word(word)
+            }
+            Self::Absurdle(_) => scoring::is_valid_word(word),
+            Self::Xordle { answers, .. } => {
+                answers.iter().any(|a| a == word) || scoring::is_valid_word(word)
+            }
+            Self::Race {
+                engines, player, ..
+            } => engines[*player].is_valid(word),
+        }
+    }
+
+    // True if the guess is one of the answers, even if the game isn't over yet
+    pub fn is_answer(&self, guess: &str) -> bool {
+        match self {
+            Self::Xordle { answers, .. } => answers.iter().any(|a| a == guess),
+            Self::Race {
+                engines, player, ..
+            } => engines[*player].is_answer(guess),
+            _ => self.is_solved(guess),
+        }
+    }
+
+    // True if the last scored guess ends the game
+    pub fn is_solved(&self, guess: &str) -> bool {
+        match self {
+            Self::Fixed(answer) | Self::Fibble { answer, .. } => guess == answer,
+            Self::Absurdle(remaining) => remaining[..] == [guess],
+            Self::Xordle { found, .. } => found.iter().all(|f| *f),
+            Self::Race {
+                engines, player, ..
+            } => engines[*player].is_solved(guess),
+        }
+    }
+
+    pub fn score(&mut self, guess: &str) -> Vec<LetterState> {
+        match self {
+            Self::Fixed(answer) => scoring::score(guess, answer),
+            Self::Absurdle(remaining) => {
+                // Keep the largest group of answers sharing the same feedback
+                let mut groups: IndexMap<Vec<LetterState>, Vec<&'static str>> = IndexMap::new();
+                for answer in remaining.iter() {
+                    groups
+                        .entry(scoring::score(guess, answer))
+                        .or_default()
+                        .push(answer);
+                }
+
+                // Ties go to the group revealing the least
+                let (states, group) = groups
+                    .into_iter()
+                    .max_by_key(|(states, group)| {
+                        let revealed: usize = states.iter().map(|s| *s as usize).sum();
+                        (group.len(), std::cmp::Reverse(revealed))
+                    })
+                    .expect("Should have answers remaining");
+
+                *remaining = group;
+                states
+            }
+            Self::Fibble { answer, lies, rng } => {
+                let mut states = scoring::score(guess, answer);
+
+                // The winning row tells the truth
+                if guess == answer {
+                    lies.push(None);
+                    return states;
+                }
+
+                let pos = rng.gen_range(0..WORD_LENGTH);
+                let truth = states[pos];
+
+                // A wrong guess mustn't look solved, so the last non-green tile stays non-green
+                let only_miss = states
+                    .iter()
+                    .enumerate()
+                    .all(|(i, ls)| i == pos || *ls == LetterState::Correct);
+                let others = [
+                    LetterState::Incorrect,
+                    LetterState::Contains,
+                    LetterState::Correct,
+                ]
+                .into_iter()
+                .filter(|ls| *ls != truth && !(only_miss && *ls == LetterState::Correct))
+                .collect::<Vec<_>>();
+                states[pos] = *others
+                    .choose(rng)
+                    .expect("Should have another colour to lie with");
+
+                lies.push(Some(pos));
+                states
+            }
+            Self::Xordle { answers, found } => {
+                for (answer, found) in answers.iter().zip(found.iter_mut()) {
+                    *found |= guess == answer;
+                }
+
+                // The answers share no letters, so each tile takes its best colour from either
+                let first = scoring::score(guess, &answers[0]);
+                let second = scoring::score(guess, &answers[1]);
+                first
+                    .into_iter()
+                    .zip(second)
+                    .map(|(a, b)| a.max(b))
+                    .collect()
+            }
+            Self::Race {
+                engines,
+                player,
+                order,
+            } => {
+                order.push(*player);
+                engines[*player].score(guess)
+            }
+        }
+    }
+
+    // An answer consistent with all feedback so far, to reveal when the player loses
+    pub fn reveal(&self) -> String {
+        match self {
+            Self::Fixed(answer) | Self::Fibble { answer, .. } => answer.clone(),
+            Self::Absurdle(remaining) => remaining.first().unwrap_or(&"").to_string(),
+            Self::Xordle { answers, .. } => answers.join("/"),
+            Self::Race { engines, .. } => engines
+                .iter()
+                .map(Self::reveal)
+                .collect::<Vec<_>>()
+                .join("/"),
+        }
+    }
+
+    // Position of the lying tile in each scored row
+    pub fn lies(&self) -> Vec<Option<usize>> {
+        match self {
+            Self::Fibble { lies, .. } => lies.clone(),
+            Self::Race { engines, order, .. } => {
+                // Interleave each player's rows in the order they were played
+                let mut lies: Vec<_> = engines.iter().map(|e| e.lies().into_iter()).collect();
+                order
+                    .iter()
+                    .map(|player| lies[*player].next().flatten())
+                    .collect()
+            }
+            _ => vec![],
+        }
+    }
+}
+
+// Random second answer with no letters in common with the first
+fn answer_pair(first: &str, rng: &mut ChaCha8Rng) -> String {
+    let pool = solver::answers()
+        .into_iter()
+        .filter(|word| !word.chars().any(|ch| first.contains(ch)))
+        .collect::<Vec<_>>();
+
+    pool.choose(rng)
+        .expect("Should have an answer with no letters in common")
+        .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn solution(answer: &str) -> Solution {
+        Solution {
+            answer: answer.to_string(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn fibble_lies_once_per_row() {
+        for seed in 0..200 {
+            let mut engine = Engine::new(Variant::Fibble, &solution("crane"), Some(seed));
+            let states = engine.score("abide");
+            let pos = engine.lies()[0].expect("Wrong guesses should lie");
+            assert_ne!(states[pos], scoring::score("abide", "crane")[pos]);
+        }
+    }
+
+    #[test]
+    fn fibble_never_shows_a_wrong_guess_as_solved() {
+        for seed in 0..200 {
+            let mut engine = Engine::new(Variant::Fibble, &solution("crane"), Some(seed));
+            let states = engine.score("crate");
+            assert!(states.iter().any(|ls| *ls != LetterState::Correct));
+        }
+    }
+
+    #[test]
+    fn fibble_tells_the_truth_when_solved() {
+        let mut engine = Engine::new(Variant::Fibble, &solution("crane"), Some(1));
+        assert_eq!(
+            engine.score("crane"),
+            vec![LetterState::Correct; WORD_LENGTH]
+        );
+        assert_eq!(engine.lies(), vec![None]);
+    }
+}
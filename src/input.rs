@@ -0,0 +1,50 @@
+use std::time::Duration;
+
+use crossterm::event::{self, Event as XtermEvent, MouseButton, MouseEvent, MouseEventKind};
+use tuirealm::{
+    listener::{ListenerError, ListenerResult, Poll},
+    Event,
+};
+
+use crate::{keymap::Keymap, model::UserEvent};
+
+// Reads the terminal like tuirealm's default listener, which drops mouse events,
+// and passes on clicks and keys bound to actions as user events
+pub struct InputListener {
+    interval: Duration,
+    keymap: Keymap,
+}
+
+impl InputListener {
+    pub fn new(interval: Duration, keymap: Keymap) -> Self {
+        Self {
+            interval: interval / 2,
+            keymap,
+        }
+    }
+}
+
+impl Poll<UserEvent> for InputListener {
+    fn poll(&mut self) -> ListenerResult<Option<Event<UserEvent>>> {
+        match event::poll(self.interval) {
+            Ok(true) => match event::read() {
+                Ok(XtermEvent::Mouse(MouseEvent {
+                    kind: MouseEventKind::Down(MouseButton::Left),
+                    column,
+                    row,
+                    ..
+                })) => Ok(Some(Event::User(UserEvent::Click { column, row }))),
+                Ok(ev) => match Event::from(ev) {
+                    Event::Keyboard(key) => match self.keymap.action(&key) {
+                        Some(action) => Ok(Some(Event::User(UserEvent::Action(action)))),
+                        None => Ok(Some(Event::Keyboard(key))),
+                    },
+                    ev => Ok(Some(ev)),
+                },
+                Err(_) => Err(ListenerError::PollFailed),
+            },
+            Ok(false) => Ok(None),
+            Err(_) => Err(ListenerError::PollFailed),
+        }
+    }
+}
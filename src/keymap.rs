@@ -0,0 +1,350 @@
+use std::fmt;
+
+use anyhow::{bail, Context, Result};
+use tuirealm::event::{Key, KeyEvent, KeyModifiers};
+
+// Things a key can do, named as in the config file
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Hash)]
+pub enum Action {
+    Submit,
+    Delete,
+    DeleteNext,
+    DeleteWord,
+    ClearLine,
+    Left,
+    Right,
+    CycleColour,
+    Hint,
+    ToggleLayout,
+    BgNext,
+    BgPrev,
+    BgReset,
+    Help,
+    Quit,
+}
+
+impl Action {
+    pub const ALL: [Action; 15] = [
+        Action::Submit,
+        Action::Delete,
+        Action::DeleteNext,
+        Action::DeleteWord,
+        Action::ClearLine,
+        Action::Left,
+        Action::Right,
+        Action::CycleColour,
+        Action::Hint,
+        Action::ToggleLayout,
+        Action::BgNext,
+        Action::BgPrev,
+        Action::BgReset,
+        Action::Help,
+        Action::Quit,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Action::Submit => "submit",
+            Action::Delete => "delete",
+            Action::DeleteNext => "delete_next",
+            Action::DeleteWord => "delete_word",
+            Action::ClearLine => "clear_line",
+            Action::Left => "left",
+            Action::Right => "right",
+            Action::CycleColour => "cycle_colour",
+            Action::Hint => "hint",
+            Action::ToggleLayout => "toggle_layout",
+            Action::BgNext => "bg_next",
+            Action::BgPrev => "bg_prev",
+            Action::BgReset => "bg_reset",
+            Action::Help => "help",
+            Action::Quit => "quit",
+        }
+    }
+
+    // Shown in the help
+    pub fn description(self) -> &'static str {
+        match self {
+            Action::Submit => "Submit the guess",
+            Action::Delete => "Delete the letter before the cursor",
+            Action::DeleteNext => "Delete the letter at the cursor",
+            Action::DeleteWord => "Delete up to the cursor",
+            Action::ClearLine => "Clear the guess",
+            Action::Left => "Move the cursor or selection left",
+            Action::Right => "Move the cursor or selection right",
+            Action::CycleColour => "Change the tile's colour",
+            Action::Hint => "Suggest a word",
+            Action::ToggleLayout => "Next keyboard layout",
+            Action::BgNext => "Next background colour",
+            Action::BgPrev => "Previous background colour",
+            Action::BgReset => "Reset the background",
+            Action::Help => "Show or hide this help",
+            Action::Quit => "Quit",
+        }
+    }
+
+    fn default_keys(self) -> Vec<KeyEvent> {
+        let ctrl = |ch| KeyEvent::new(Key::Char(ch), KeyModifiers::CONTROL);
+        let key = |code| KeyEvent::new(code, KeyModifiers::NONE);
+        match self {
+            Action::Submit => vec![key(Key::Enter)],
+            Action::Delete => vec![key(Key::Backspace)],
+            Action::DeleteNext => vec![key(Key::Delete)],
+            Action::DeleteWord => vec![ctrl('w')],
+            Action::ClearLine => vec![ctrl('u')],
+            Action::Left => vec![key(Key::Left)],
+            Action::Right => vec![key(Key::Right)],
+            Action::CycleColour => vec![key(Key::Up), key(Key::Down), key(Key::Char(' '))],
+            Action::Hint => vec![key(Key::Function(2))],
+            Action::ToggleLayout => vec![key(Key::Tab)],
+            Action::BgNext => vec![key(Key::PageDown)],
+            Action::BgPrev => vec![key(Key::PageUp)],
+            Action::BgReset => vec![key(Key::Home)],
+            Action::Help => vec![key(Key::Function(1)), key(Key::Char('?'))],
+            Action::Quit => vec![key(Key::Esc), ctrl('c')],
+        }
+    }
+}
+
+impl std::str::FromStr for Action {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        Action::ALL
+            .into_iter()
+            .find(|action| action.name() == s)
+            .with_context(|| {
+                format!(
+                    "Unknown action {}, expected one of {}",
+                    s,
+                    Action::ALL.map(Action::name).join(", ")
+                )
+            })
+    }
+}
+
+// Keys for each action, from the defaults and the config
+#[derive(Debug, Clone, PartialEq)]
+pub struct Keymap {
+    bindings: Vec<(KeyEvent, Action)>,
+}
+
+impl Default for Keymap {
+    fn default() -> Self {
+        let bindings = Action::ALL
+            .into_iter()
+            .flat_map(|action| {
+                action
+                    .default_keys()
+                    .into_iter()
+                    .map(move |key| (key, action))
+            })
+            .collect();
+        Self { bindings }
+    }
+}
+
+impl Keymap {
+    // Replaces the keys for an action, e.g. ["Ctrl-W", "Ctrl-U"]
+    pub fn bind(&mut self, action: Action, keys: &[String]) -> Result<()> {
+        let keys = keys
+            .iter()
+            .map(|key| parse_key(key))
+            .collect::<Result<Vec<_>>>()?;
+
+        self.bindings
+            .retain(|(key, bound)| *bound != action && !keys.contains(key));
+        self.bindings
+            .extend(keys.into_iter().map(|key| (key, action)));
+        Ok(())
+    }
+
+    pub fn action(&self, key: &KeyEvent) -> Option<Action> {
+        let key = normalise(*key);
+        self.bindings
+            .iter()
+            .find(|(bound, _)| *bound == key)
+            .map(|(_, action)| *action)
+    }
+
+    // E.g. "Esc, Ctrl-C"
+    pub fn keys(&self, action: Action) -> String {
+        let keys: Vec<String> = self
+            .bindings
+            .iter()
+            .filter(|(_, bound)| *bound == action)
+            .map(|(key, _)| KeyName(*key).to_string())
+            .collect();
+        keys.join(", ")
+    }
+}
+
+// Shift is part of the character typed, so only counts for other keys
+fn normalise(key: KeyEvent) -> KeyEvent {
+    match key.code {
+        Key::Char(ch) => KeyEvent::new(
+            Key::Char(ch.to_ascii_lowercase()),
+            key.modifiers - KeyModifiers::SHIFT,
+        ),
+        _ => key,
+    }
+}
+
+// E.g. "Enter", "Ctrl-W", "Alt-Left", "F1" or "?"
+fn parse_key(s: &str) -> Result<KeyEvent> {
+    let mut modifiers = KeyModifiers::NONE;
+    let mut name = s;
+    while let Some((prefix, rest)) = name.split_once('-').filter(|(_, rest)| !rest.is_empty()) {
+        modifiers |= match prefix.to_ascii_lowercase().as_str() {
+            "ctrl" => KeyModifiers::CONTROL,
+            "alt" => KeyModifiers::ALT,
+            "shift" => KeyModifiers::SHIFT,
+            _ => break,
+        };
+        name = rest;
+    }
+
+    let code = match name.to_ascii_lowercase().as_str() {
+        "enter" => Key::Enter,
+        "esc" => Key::Esc,
+        "backspace" => Key::Backspace,
+        "delete" => Key::Delete,
+        "tab" => Key::Tab,
+        "space" => Key::Char(' '),
+        "left" => Key::Left,
+        "right" => Key::Right,
+        "up" => Key::Up,
+        "down" => Key::Down,
+        "home" => Key::Home,
+        "end" => Key::End,
+        "pageup" => Key::PageUp,
+        "pagedown" => Key::PageDown,
+        "insert" => Key::Insert,
+        f if f.starts_with('f') && f[1..].parse::<u8>().is_ok() => {
+            Key::Function(f[1..].parse().expect("Function key number was checked"))
+        }
+        _ => {
+            let mut chars = name.chars();
+            match (chars.next(), chars.next()) {
+                (Some(ch), None) => Key::Char(ch),
+                _ => bail!("Unknown key {}", s),
+            }
+        }
+    };
+
+    // Letters are needed for guesses
+    if matches!(code, Key::Char(ch) if ch.is_ascii_alphabetic())
+        && (modifiers - KeyModifiers::SHIFT).is_empty()
+    {
+        bail!("Key {} is needed for typing guesses, add Ctrl or Alt", s);
+    }
+
+    Ok(normalise(KeyEvent::new(code, modifiers)))
+}
+
+struct KeyName(KeyEvent);
+
+impl fmt::Display for KeyName {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let KeyEvent { code, modifiers } = self.0;
+        if modifiers.contains(KeyModifiers::CONTROL) {
+            write!(f, "Ctrl-")?;
+        }
+        if modifiers.contains(KeyModifiers::ALT) {
+            write!(f, "Alt-")?;
+        }
+        if modifiers.contains(KeyModifiers::SHIFT) {
+            write!(f, "Shift-")?;
+        }
+        match code {
+            Key::Char(' ') => write!(f, "Space"),
+            Key::Char(ch) if modifiers.is_empty() => write!(f, "{}", ch),
+            Key::Char(ch) => write!(f, "{}", ch.to_ascii_uppercase()),
+            Key::Function(n) => write!(f, "F{}", n),
+            Key::PageUp => write!(f, "PageUp"),
+            Key::PageDown => write!(f, "PageDown"),
+            code => write!(f, "{:?}", code),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key(code: Key, modifiers: KeyModifiers) -> KeyEvent {
+        KeyEvent::new(code, modifiers)
+    }
+
+    #[test]
+    fn parses_keys() {
+        assert_eq!(
+            parse_key("Ctrl-W").unwrap(),
+            key(Key::Char('w'), KeyModifiers::CONTROL)
+        );
+        assert_eq!(
+            parse_key("alt-shift-left").unwrap(),
+            key(Key::Left, KeyModifiers::ALT | KeyModifiers::SHIFT)
+        );
+        assert_eq!(
+            parse_key("F12").unwrap(),
+            key(Key::Function(12), KeyModifiers::NONE)
+        );
+        assert_eq!(
+            parse_key("Space").unwrap(),
+            key(Key::Char(' '), KeyModifiers::NONE)
+        );
+        assert_eq!(
+            parse_key("?").unwrap(),
+            key(Key::Char('?'), KeyModifiers::NONE)
+        );
+        assert_eq!(
+            parse_key("-").unwrap(),
+            key(Key::Char('-'), KeyModifiers::NONE)
+        );
+        assert_eq!(
+            parse_key("Ctrl--").unwrap(),
+            key(Key::Char('-'), KeyModifiers::CONTROL)
+        );
+    }
+
+    #[test]
+    fn rejects_unknown_keys_and_letters() {
+        assert!(parse_key("Ctrl-Foo").is_err());
+        assert!(parse_key("Hyper-W").is_err());
+        assert!(parse_key("").is_err());
+        assert!(parse_key("q").is_err());
+        assert!(parse_key("Shift-Q").is_err());
+    }
+
+    #[test]
+    fn binding_replaces_the_defaults() {
+        let mut keymap = Keymap::default();
+        let ctrl_w = key(Key::Char('w'), KeyModifiers::CONTROL);
+        let ctrl_u = key(Key::Char('u'), KeyModifiers::CONTROL);
+        assert_eq!(keymap.action(&ctrl_w), Some(Action::DeleteWord));
+
+        keymap.bind(Action::ClearLine, &["Ctrl-W".into()]).unwrap();
+        assert_eq!(keymap.action(&ctrl_w), Some(Action::ClearLine));
+        assert_eq!(keymap.action(&ctrl_u), None);
+        assert_eq!(keymap.keys(Action::DeleteWord), "");
+        assert_eq!(keymap.keys(Action::ClearLine), "Ctrl-W");
+    }
+
+    #[test]
+    fn shift_is_ignored_for_characters() {
+        let keymap = Keymap::default();
+        assert_eq!(
+            keymap.action(&key(Key::Char('?'), KeyModifiers::SHIFT)),
+            Some(Action::Help)
+        );
+        assert_eq!(
+            keymap.action(&key(
+                Key::Char('C'),
+                KeyModifiers::CONTROL | KeyModifiers::SHIFT
+            )),
+            Some(Action::Quit)
+        );
+        assert_eq!(keymap.keys(Action::Quit), "Esc, Ctrl-C");
+    }
+}
@@ -0,0 +1,358 @@
+use std::{
+    io::{self, BufRead, BufReader, Write},
+    net::{Shutdown, SocketAddr, TcpListener, TcpStream, ToSocketAddrs},
+    sync::{
+        mpsc::{self, Sender},
+        Arc, Mutex, RwLock,
+    },
+    thread,
+    time::Duration,
+};
+
+use anyhow::{bail, Context, Result};
+use indexmap::IndexMap;
+use serde_json::{json, Value};
+
+use crate::{engine::Variant, provider::Solution, scoring::WORD_LENGTH, share, ResultGrid};
+
+pub const DEFAULT_PORT: u16 = 4545;
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(5);
+// A player whose connection stalls for this long is dropped rather than holding up the others
+const WRITE_TIMEOUT: Duration = Duration::from_secs(5);
+
+// Game handed out by the server, the same for every player
+pub struct Game {
+    pub solution: Solution,
+    pub variant: Variant,
+    pub max_guesses: Option<usize>,
+    pub seed: u64,
+}
+
+// Colour-only progress of another player, without the letters they guessed
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct Opponent {
+    pub grid: Vec<String>, // Rows as patterns, e.g. "GY..G"
+    pub solved: bool,
+    pub finished: bool,
+    pub left: bool,
+}
+
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct Opponents {
+    pub players: IndexMap<String, Opponent>,
+    pub connected: bool,
+}
+
+// Connection to a `turdle serve` game
+pub struct Lan {
+    stream: TcpStream,
+    // Updated by the network thread, so unlike other shared state this needs Arc
+    pub opponents: Arc<RwLock<Opponents>>,
+}
+
+impl Lan {
+    // Sends the colours of each row, never the letters
+    pub fn send_progress(&self, rg: &ResultGrid, finished: bool) -> Result<()> {
+        let grid: Vec<String> = rg.grid.iter().map(|line| share::pattern(line)).collect();
+        send(
+            &self.stream,
+            &json!({
+                "type": "progress",
+                "grid": grid,
+                "solved": rg.solved,
+                "finished": finished,
+            }),
+        )?;
+        Ok(())
+    }
+}
+
+struct Client {
+    name: String,
+    outbox: Sender<Value>, // Written to the player's stream by their own writer thread
+    progress: Option<Value>, // Latest progress, sent to players joining later
+}
+
+type Clients = Arc<Mutex<IndexMap<usize, Client>>>;
+
+// Hands out the same game to every player who joins and relays their progress
+pub fn serve(addr: &str, game: Game) -> Result<()> {
+    let listener =
+        TcpListener::bind(addr).with_context(|| format!("Could not listen on {}", addr))?;
+    let port = listener.local_addr()?.port();
+    println!("Serving a race on port {}, press Ctrl-C to stop", port);
+    println!(
+        "Players can join with `turdle join <this computer's address>:{}`",
+        port
+    );
+
+    let start = json!({
+        "type": "start",
+        "answer": game.solution.answer,
+        "variant": share::variant_name(game.variant),
+        "max_guesses": game.max_guesses,
+        "seed": game.seed,
+    });
+    let clients: Clients = Default::default();
+
+    for (id, stream) in listener.incoming().enumerate() {
+        let Ok(stream) = stream else {
+            continue;
+        };
+        let clients = Arc::clone(&clients);
+        let start = start.clone();
+
+        thread::spawn(move || {
+            if let Err(e) = handle_client(id, stream, &clients, &start) {
+                eprintln!("(Player disconnected: {})", e);
+            }
+
+            let gone = clients.lock().unwrap().shift_remove(&id);
+            if let Some(client) = gone {
+                println!("{} left", client.name);
+                broadcast(&clients, id, &json!({"type": "left", "name": client.name}));
+            }
+        });
+    }
+
+    Ok(())
+}
+
+fn handle_client(id: usize, stream: TcpStream, clients: &Clients, start: &Value) -> Result<()> {
+    stream.set_write_timeout(Some(WRITE_TIMEOUT))?;
+    let mut reader = BufReader::new(stream.try_clone()?);
+    let mut line = String::new();
+    reader.read_line(&mut line)?;
+    let hello: Value = serde_json::from_str(&line).context("Invalid hello message")?;
+    if hello["type"] != "hello" {
+        bail!("Expected a hello message");
+    }
+
+    let name = {
+        let mut clients = clients.lock().unwrap();
+        let name = unique_name(hello["name"].as_str().unwrap_or("Player"), &clients);
+
+        let outbox = writer(stream);
+        let mut start = start.clone();
+        start["name"] = json!(name);
+        let _ = outbox.send(start);
+
+        // Catch up on the other players' progress
+        for client in clients.values() {
+            if let Some(progress) = &client.progress {
+                let _ = outbox.send(progress.clone());
+            }
+        }
+
+        clients.insert(
+            id,
+            Client {
+                name: name.clone(),
+                outbox,
+                progress: None,
+            },
+        );
+        name
+    };
+    println!("{} joined", name);
+
+    // Show the new player to everyone else straight away
+    store_progress(clients, id, progress(&name, vec![], false, false));
+
+    for line in reader.lines() {
+        let Ok(msg) = serde_json::from_str::<Value>(&line?) else {
+            continue;
+        };
+        if msg["type"] != "progress" {
+            continue;
+        }
+
+        // Pass on colours only, in case a client sends more
+        let grid: Vec<String> = msg["grid"]
+            .as_array()
+            .map(|rows| rows.iter().filter_map(Value::as_str).map(colours).collect())
+            .unwrap_or_default();
+        let solved = msg["solved"].as_bool().unwrap_or(false);
+        let finished = msg["finished"].as_bool().unwrap_or(false);
+
+        match (solved, finished) {
+            (true, _) => println!("{} solved it in {}", name, grid.len()),
+            (false, true) => println!("{} missed it", name),
+            _ => println!("{}: {}", name, grid.last().map_or("", String::as_str)),
+        }
+        store_progress(clients, id, progress(&name, grid, solved, finished));
+    }
+
+    Ok(())
+}
+
+fn progress(name: &str, grid: Vec<String>, solved: bool, finished: bool) -> Value {
+    json!({
+        "type": "progress",
+        "name": name,
+        "grid": grid,
+        "solved": solved,
+        "finished": finished,
+    })
+}
+
+// Keeps a player's latest progress for latecomers and passes it on to the others
+fn store_progress(clients: &Clients, id: usize, progress: Value) {
+    if let Some(client) = clients.lock().unwrap().get_mut(&id) {
+        client.progress = Some(progress.clone());
+    }
+    broadcast(clients, id, &progress);
+}
+
+// Writes queued messages to a player in order, so a slow connection never holds the lock
+fn writer(stream: TcpStream) -> Sender<Value> {
+    let (outbox, messages) = mpsc::channel::<Value>();
+    thread::spawn(move || {
+        for msg in messages {
+            if send(&stream, &msg).is_err() {
+                // Ends the player's reader thread, which removes them
+                let _ = stream.shutdown(Shutdown::Both);
+                break;
+            }
+        }
+    });
+    outbox
+}
+
+// Sends a message to every player except the sender
+fn broadcast(clients: &Clients, from: usize, msg: &Value) {
+    for (id, client) in clients.lock().unwrap().iter() {
+        // Players that have gone are removed by their own thread
+        if *id != from {
+            let _ = client.outbox.send(msg.clone());
+        }
+    }
+}
+
+// E.g. "Sam" or "Sam 2" if there's already a Sam
+fn unique_name(name: &str, clients: &IndexMap<usize, Client>) -> String {
+    let name = name.trim();
+    let name = if name.is_empty() { "Player" } else { name };
+    let taken = |candidate: &str| clients.values().any(|c| c.name == candidate);
+
+    let mut unique = name.to_string();
+    let mut n = 1;
+    while taken(&unique) {
+        n += 1;
+        unique = format!("{} {}", name, n);
+    }
+    unique
+}
+
+fn colours(row: &str) -> String {
+    row.chars()
+        .map(|ch| match ch {
+            'G' | 'Y' => ch,
+            _ => '.',
+        })
+        .collect()
+}
+
+// Joins a game hosted with `turdle serve`, returning the game to play
+pub fn connect(addr: &str, name: &str) -> Result<(Game, Lan)> {
+    // The port is optional
+    let has_port = addr.parse::<SocketAddr>().is_ok()
+        || addr
+            .rsplit_once(':')
+            .is_some_and(|(_, port)| port.parse::<u16>().is_ok());
+    let addr = if has_port {
+        addr.to_string()
+    } else {
+        format!("{}:{}", addr, DEFAULT_PORT)
+    };
+
+    let socket = addr
+        .to_socket_addrs()
+        .with_context(|| format!("Could not find {}", addr))?
+        .next()
+        .with_context(|| format!("Could not find {}", addr))?;
+    let stream = TcpStream::connect_timeout(&socket, CONNECT_TIMEOUT)
+        .with_context(|| format!("Could not connect to {}", addr))?;
+    stream.set_write_timeout(Some(WRITE_TIMEOUT))?;
+    send(&stream, &json!({"type": "hello", "name": name}))?;
+
+    stream.set_read_timeout(Some(CONNECT_TIMEOUT))?;
+    let mut reader = BufReader::new(stream.try_clone()?);
+    let mut line = String::new();
+    reader
+        .read_line(&mut line)
+        .context("No response from the server")?;
+    stream.set_read_timeout(None)?;
+
+    let start: Value = serde_json::from_str(&line).context("Invalid response from the server")?;
+    if start["type"] != "start" {
+        bail!("Invalid response from the server");
+    }
+    let answer = start["answer"]
+        .as_str()
+        .context("Server didn't send a word")?;
+    if answer.len() != WORD_LENGTH || !answer.chars().all(|ch| ch.is_ascii_alphabetic()) {
+        bail!("Server sent an invalid word");
+    }
+    let game = Game {
+        solution: Solution {
+            answer: answer.to_ascii_lowercase(),
+            ..Default::default()
+        },
+        variant: start["variant"].as_str().unwrap_or("classic").parse()?,
+        max_guesses: start["max_guesses"].as_u64().map(|n| n as usize),
+        seed: start["seed"].as_u64().unwrap_or_default(),
+    };
+
+    let opponents = Arc::new(RwLock::new(Opponents {
+        players: IndexMap::new(),
+        connected: true,
+    }));
+    let shared = Arc::clone(&opponents);
+    thread::spawn(move || {
+        for line in reader.lines() {
+            let Ok(Ok(msg)) = line.map(|line| serde_json::from_str::<Value>(&line)) else {
+                break;
+            };
+            let name = msg["name"].as_str().unwrap_or_default().to_string();
+            let mut opponents = shared.write().unwrap();
+
+            match msg["type"].as_str() {
+                Some("progress") => {
+                    let grid = msg["grid"]
+                        .as_array()
+                        .map(|rows| {
+                            rows.iter()
+                                .filter_map(Value::as_str)
+                                .map(String::from)
+                                .collect()
+                        })
+                        .unwrap_or_default();
+                    opponents.players.insert(
+                        name,
+                        Opponent {
+                            grid,
+                            solved: msg["solved"].as_bool().unwrap_or(false),
+                            finished: msg["finished"].as_bool().unwrap_or(false),
+                            left: false,
+                        },
+                    );
+                }
+                Some("left") => {
+                    if let Some(opponent) = opponents.players.get_mut(&name) {
+                        opponent.left = true;
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        shared.write().unwrap().connected = false;
+    });
+
+    Ok((game, Lan { stream, opponents }))
+}
+
+fn send(mut stream: &TcpStream, msg: &Value) -> io::Result<()> {
+    writeln!(stream, "{}", msg)
+}
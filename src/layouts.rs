@@ -0,0 +1,89 @@
+use anyhow::{bail, Result};
+
+// Rows of the on-screen keyboard, letters only
+const BUILTIN: [(&str, [&str; 3]); 6] = [
+    ("alphabetical", ["abcdefghijklm", "nopqrstuvwxyz", ""]),
+    ("qwerty", ["qwertyuiop", "asdfghjkl", "zxcvbnm"]),
+    ("azerty", ["azertyuiop", "qsdfghjklm", "wxcvbn"]),
+    ("qwertz", ["qwertzuiop", "asdfghjkl", "yxcvbnm"]),
+    ("dvorak", ["pyfgcrl", "aoeuidhtns", "qjkxbmwvz"]),
+    ("colemak", ["qwfpgjluy", "arstdhneio", "zxcvbkm"]),
+];
+
+// Layouts Tab cycles through when the config doesn't say
+const DEFAULT: [&str; 2] = ["alphabetical", "qwerty"];
+
+pub const MAX_ROWS: usize = 3;
+const MAX_ROW_LETTERS: usize = 13;
+const MAX_LAST_ROW_LETTERS: usize = 10; // Leaving room for Enter and Delete
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct KeyboardLayout {
+    pub name: String,
+    pub rows: Vec<Vec<char>>,
+}
+
+impl KeyboardLayout {
+    // Layout from the config, which must have every letter exactly once
+    pub fn custom(name: &str, rows: &[String]) -> Result<Self> {
+        if rows.is_empty() || rows.len() > MAX_ROWS {
+            bail!("Layout {} should have 1 to {} rows", name, MAX_ROWS);
+        }
+
+        let rows: Vec<Vec<char>> = rows
+            .iter()
+            .map(|row| row.to_ascii_lowercase().chars().collect())
+            .collect();
+        let too_long = rows.iter().enumerate().any(|(i, row)| {
+            let max = if i == MAX_ROWS - 1 {
+                MAX_LAST_ROW_LETTERS
+            } else {
+                MAX_ROW_LETTERS
+            };
+            row.len() > max
+        });
+        if too_long {
+            bail!(
+                "Layout {} has too many letters in a row to fit on screen (at most {}, or {} in row {})",
+                name,
+                MAX_ROW_LETTERS,
+                MAX_LAST_ROW_LETTERS,
+                MAX_ROWS
+            );
+        }
+
+        let mut letters: Vec<char> = rows.concat();
+        letters.sort_unstable();
+        if letters != ('a'..='z').collect::<Vec<_>>() {
+            bail!("Layout {} should have each letter from a to z once", name);
+        }
+
+        Ok(Self {
+            name: name.to_string(),
+            rows,
+        })
+    }
+
+    pub fn is_qwerty(&self) -> bool {
+        self.name == "qwerty"
+    }
+}
+
+pub fn builtin(name: &str) -> Option<KeyboardLayout> {
+    let name = name.to_ascii_lowercase();
+    BUILTIN
+        .iter()
+        .find(|(builtin, _)| *builtin == name)
+        .map(|(name, rows)| KeyboardLayout {
+            name: name.to_string(),
+            rows: rows.iter().map(|row| row.chars().collect()).collect(),
+        })
+}
+
+pub fn names() -> Vec<&'static str> {
+    BUILTIN.iter().map(|(name, _)| *name).collect()
+}
+
+pub fn defaults() -> Vec<KeyboardLayout> {
+    DEFAULT.iter().filter_map(|name| builtin(name)).collect()
+}
@@ -1,19 +1,52 @@
 extern crate tuirealm;
 
-use std::io::stdout;
+use std::{
+    fs,
+    io::{self, ErrorKind, IsTerminal, Write},
+    path::PathBuf,
+    process::ExitCode,
+    rc::Rc,
+    sync::{Arc, RwLock},
+    time::{Duration, Instant},
+};
 
 use anyhow::{bail, Result};
-use clap::{arg, Command};
-use crossterm::{execute, style::Print};
+use bot::BOT_ANSWERS;
+use clap::{arg, value_parser, ArgAction, ArgMatches, Command};
+use comp::{
+    board::BoardMode,
+    players::{PlayerStyle, Players, PLAYER_STYLES},
+    timer::format_time,
+};
+use config::Config;
+use engine::{Variant, VARIANTS};
 use model::{LetterState, Model};
 use provider::{Solution, SolutionProvider};
+use share::{ShareFormat, SHARE_FORMATS};
+use solver::Constraints;
 use time::{Date, OffsetDateTime, Time};
 
+mod bot;
+mod challenge;
+mod clipboard;
 mod comp;
+mod config;
 mod data;
+mod engine;
+mod input;
+mod keymap;
+mod lan;
+mod layouts;
 mod model;
+mod plain;
 mod provider;
+mod scoring;
+mod screen;
+mod share;
+mod solver;
+mod stats;
 mod theme;
+mod tools;
 
 const APP_NAME: &str = env!("CARGO_PKG_NAME");
 const APP_VERSION: &str = env!("CARGO_PKG_VERSION");
@@ -23,14 +56,96 @@ const APP_DESC: &str = env!("CARGO_PKG_DESCRIPTION");
 #[derive(Debug, Clone, PartialEq)]
 pub struct ResultGrid {
     solution: Solution,
-    lines_used: u8,
+    variant: Variant,
+    lies: Vec<Option<usize>>,   // Lying tile in each row for Fibble games
+    max_guesses: Option<usize>, // None if unlimited
+    solved: bool,
+    lines_used: usize,
+    guesses: Vec<String>,
+    players: Vec<String>,  // Hot-seat player who made each guess
+    winner: Option<usize>, // Hot-seat player who solved it
     grid: Vec<Vec<LetterState>>,
+    duration: Duration,
+    time: Option<Duration>, // Timer reading for timed games
+    splits: Vec<Duration>,  // Timer reading at each guess
+}
+
+impl ResultGrid {
+    // Position of the tile that lied in a row, if any
+    fn lie(&self, row: usize) -> Option<usize> {
+        self.lies.get(row).copied().flatten()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Outcome {
+    Won,
+    Lost,
+    Quit,
+}
+
+impl Outcome {
+    // Quit if the game wasn't finished
+    fn of(result_grid: Option<&ResultGrid>) -> Self {
+        match result_grid {
+            Some(rg) if rg.solved => Outcome::Won,
+            Some(_) => Outcome::Lost,
+            None => Outcome::Quit,
+        }
+    }
+
+    // Errors exit with 1 and invalid arguments with 2
+    fn exit_code(self) -> ExitCode {
+        match self {
+            Outcome::Won => ExitCode::SUCCESS,
+            Outcome::Lost => ExitCode::from(3),
+            Outcome::Quit => ExitCode::from(4),
+        }
+    }
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub enum OutputFormat {
+    #[default]
+    Text,
+    Json,
 }
 
 pub enum AppMode {
     Random,
     Today(OffsetDateTime),
     Date(OffsetDateTime),
+    Assist,
+    Reverse,
+    Marathon(Duration),
+    Challenge(String),
+    Host(Option<String>), // Name of the player setting the word
+    Hotseat(Vec<String>, PlayerStyle),
+}
+
+#[derive(Clone)]
+pub struct Options {
+    variant: Variant,
+    timed: bool,
+    time_limit: Option<Duration>,
+    marathon: bool,                       // Finished games lead on to the next word
+    plain: bool,                          // Line mode instead of the full-screen board
+    inline: bool,                         // Board drawn below the prompt instead of full-screen
+    max_guesses: Option<usize>,           // None if unlimited
+    players: Option<Rc<RwLock<Players>>>, // Set for hot-seat games
+    seed: Option<u64>,
+    share_format: ShareFormat,
+    copy: bool,
+    output: OutputFormat,
+    output_file: Option<PathBuf>,
+    config: Config,
+    resume: Vec<String>, // Guesses replayed from an unfinished game saved by a crash
+}
+
+impl Options {
+    fn json_to_stdout(&self) -> bool {
+        self.output == OutputFormat::Json && self.output_file.is_none()
+    }
 }
 
 fn cli() -> Command {
@@ -40,6 +155,64 @@ fn cli() -> Command {
         .about(APP_DESC)
         .subcommand_required(false)
         .allow_external_subcommands(false)
+        .arg(
+            arg!(--variant <VARIANT> "Game variant to play")
+                .value_parser(VARIANTS)
+                .default_value("classic")
+                .global(true),
+        )
+        .arg(
+            arg!(--guesses <N> "Number of guesses allowed")
+                .value_parser(value_parser!(u8).range(1..=99))
+                .default_value("6")
+                .global(true),
+        )
+        .arg(
+            arg!(--zen "Unlimited guesses, scrolling older rows out of view")
+                .conflicts_with("guesses")
+                .global(true),
+        )
+        .arg(
+            arg!(--seed <SEED> "Seed for the random answer, so others can play the same word")
+                .value_parser(parse_seed)
+                .global(true),
+        )
+        .arg(arg!(--timed "Show a timer and record the time taken by each guess").global(true))
+        .arg(
+            arg!(--"time-limit" <TIME> "Lose if the word isn't found in time, e.g. 90s or 2m")
+                .value_parser(parse_time)
+                .global(true),
+        )
+        .arg(
+            arg!(--plain "Play a line at a time, for screen readers, basic terminals and piped input")
+                .global(true),
+        )
+        .arg(
+            arg!(--inline "Draw a compact board below the prompt and leave it in the scrollback")
+                .conflicts_with("plain")
+                .global(true),
+        )
+        .arg(
+            arg!(--"share-format" <FORMAT> "Format of the result printed after the game")
+                .value_parser(SHARE_FORMATS)
+                .default_value("emoji")
+                .global(true),
+        )
+        .arg(
+            arg!(--copy "Copy the result to the clipboard after the game (via OSC 52)")
+                .global(true),
+        )
+        .arg(
+            arg!(--output <FORMAT> "Write a machine-readable report of the game")
+                .value_parser(["text", "json"])
+                .default_value("text")
+                .global(true),
+        )
+        .arg(
+            arg!(--"output-file" <PATH> "Write the report to a file instead of stdout")
+                .value_parser(value_parser!(PathBuf))
+                .global(true),
+        )
         .subcommand(Command::new("random").about("Pick a random word (default)"))
         .subcommand(Command::new("today").about("Fetch today's solution from NYT"))
         .subcommand(
@@ -48,10 +221,147 @@ fn cli() -> Command {
                 .arg(arg!(date: <DATE> "The date in [YY]YY-MM-DD format"))
                 .arg_required_else_help(true),
         )
+        .subcommand(
+            Command::new("assist")
+                .about("Enter the feedback from a game played elsewhere to get suggestions"),
+        )
+        .subcommand(
+            Command::new("reverse").about("Think of a word and let the computer try to guess it"),
+        )
+        .subcommand(
+            Command::new("marathon")
+                .about("Solve as many random words as possible within the time")
+                .arg(
+                    arg!([MINUTES] "Length of the marathon")
+                        .value_parser(value_parser!(u64).range(1..))
+                        .default_value("5"),
+                ),
+        )
+        .subcommand(
+            Command::new("challenge")
+                .about("Play a word chosen by someone else from its challenge code")
+                .arg(arg!([CODE] "The challenge code"))
+                .args_conflicts_with_subcommands(true)
+                .arg_required_else_help(true)
+                .subcommand(
+                    Command::new("create")
+                        .about("Create a challenge code for a word")
+                        .arg(arg!(<WORD> "Any valid word")),
+                ),
+        )
+        .subcommand(
+            Command::new("host")
+                .about("Choose the word for someone else to guess")
+                .arg(arg!(--name <NAME> "Your name, revealed if the word isn't guessed")),
+        )
+        .subcommand(
+            Command::new("hotseat")
+                .about("Two players take turns guessing on the same board")
+                .arg(
+                    arg!([PLAYERS] "Names of the two players")
+                        .num_args(2)
+                        .value_names(["NAME", "NAME"]),
+                )
+                .arg(
+                    arg!(--style <STYLE> "Share one word (turns) or race to your own word (race)")
+                        .value_parser(PLAYER_STYLES)
+                        .default_value("turns"),
+                ),
+        )
+        .subcommand(
+            Command::new("serve")
+                .about("Host a race on the local network, giving every player the same word")
+                .arg(
+                    arg!(--port <PORT> "Port to listen on")
+                        .value_parser(value_parser!(u16))
+                        .default_value("4545"),
+                )
+                .arg(arg!(--bind <ADDR> "Address to listen on").default_value("0.0.0.0")),
+        )
+        .subcommand(
+            Command::new("join")
+                .about("Join a race hosted with `turdle serve`")
+                .arg(arg!(<ADDR> "Address of the server, e.g. 192.168.1.20 or 192.168.1.20:4545"))
+                .arg(arg!(--name <NAME> "Your name, shown to the other players")),
+        )
+        .subcommand(
+            Command::new("bot")
+                .about("Let a program play, exchanging JSON lines over stdin and stdout")
+                .arg(
+                    arg!(--answers <WHICH> "Play one random word, or every word in the answer list")
+                        .value_parser(BOT_ANSWERS)
+                        .default_value("one"),
+                ),
+        )
+        .subcommand(Command::new("stats").about("Show statistics for previous games"))
+        .subcommand(
+            Command::new("check")
+                .about("Score guesses against an answer without playing")
+                .arg(arg!(--answer <WORD> "The answer to score against").required(true))
+                .arg(arg!([GUESS] ... "Guesses to score (read from stdin if omitted)")),
+        )
+        .subcommand(
+            Command::new("search")
+                .about("List the words matching Wordle-style constraints")
+                .arg(arg!([PATTERN] "Known letters by position, e.g. cr_n_"))
+                .arg(arg!(--has <LETTERS> "Letters in the word").action(ArgAction::Append))
+                .arg(arg!(--lacks <LETTERS> "Letters not in the word").action(ArgAction::Append))
+                .arg(
+                    arg!(--"not-at" <SPEC> "Letters in the word but not at a position, e.g. a:2")
+                        .action(ArgAction::Append),
+                )
+                .arg(
+                    arg!(--guess <SPEC> "A guess and its feedback (G, Y or .), e.g. crane:..Y.G")
+                        .action(ArgAction::Append),
+                )
+                .arg(arg!(--words "Search all valid words instead of only answers"))
+                .arg(
+                    arg!(--sort <ORDER> "Order of the results")
+                        .value_parser(["alpha", "freq"])
+                        .default_value("alpha"),
+                )
+                .arg(
+                    arg!(--limit <N> "Maximum number of results to print")
+                        .value_parser(value_parser!(usize)),
+                ),
+        )
 }
 
-fn parse_cli() -> Result<AppMode> {
-    let matches = cli().get_matches();
+fn parse_options(matches: &ArgMatches) -> Result<Options> {
+    Ok(Options {
+        variant: matches
+            .get_one::<String>("variant")
+            .expect("Variant has a default value")
+            .parse()?,
+        seed: matches.get_one::<u64>("seed").copied(),
+        timed: matches.get_flag("timed") || matches.contains_id("time-limit"),
+        time_limit: matches.get_one::<Duration>("time-limit").copied(),
+        marathon: false,
+        plain: matches.get_flag("plain"),
+        inline: matches.get_flag("inline"),
+        // Only the board uses the config, so it's loaded once a board mode is chosen
+        config: Config::default(),
+        players: None,
+        max_guesses: if matches.get_flag("zen") {
+            None
+        } else {
+            matches.get_one::<u8>("guesses").map(|n| *n as usize)
+        },
+        share_format: matches
+            .get_one::<String>("share-format")
+            .expect("Share format has a default value")
+            .parse()?,
+        copy: matches.get_flag("copy"),
+        output: match matches.get_one::<String>("output").map(String::as_str) {
+            Some("json") => OutputFormat::Json,
+            _ => OutputFormat::Text,
+        },
+        output_file: matches.get_one::<PathBuf>("output-file").cloned(),
+        resume: vec![],
+    })
+}
+
+fn parse_mode(matches: &ArgMatches) -> Result<AppMode> {
     match matches.subcommand() {
         Some(("today", _)) => {
             let today = OffsetDateTime::now_local()?;
@@ -100,79 +410,696 @@ fn parse_cli() -> Result<AppMode> {
 
         Some(("random", _)) => Ok(AppMode::Random),
 
+        Some(("assist", _)) => Ok(AppMode::Assist),
+
+        Some(("reverse", _)) => Ok(AppMode::Reverse),
+
+        Some(("challenge", args)) => {
+            let code = args
+                .get_one::<String>("CODE")
+                .expect("Code should be provided");
+            Ok(AppMode::Challenge(code.clone()))
+        }
+
+        Some(("host", args)) => Ok(AppMode::Host(args.get_one::<String>("name").cloned())),
+
+        Some(("hotseat", args)) => {
+            let names = args
+                .get_many::<String>("PLAYERS")
+                .map(|names| names.cloned().collect())
+                .unwrap_or_else(|| vec!["Player 1".into(), "Player 2".into()]);
+            // Rounds and scores are shown by name, so each must be different
+            if names
+                .iter()
+                .enumerate()
+                .any(|(i, name)| names[..i].contains(name))
+            {
+                bail!("Hot-seat players need different names");
+            }
+            let style = match args.get_one::<String>("style").map(String::as_str) {
+                Some("race") => PlayerStyle::Race,
+                _ => PlayerStyle::Turns,
+            };
+            Ok(AppMode::Hotseat(names, style))
+        }
+
+        Some(("marathon", args)) => {
+            let minutes = args
+                .get_one::<u64>("MINUTES")
+                .expect("Minutes has a default value");
+            Ok(AppMode::Marathon(Duration::from_secs(minutes * 60)))
+        }
+
         None => Ok(AppMode::Random), // Default
 
         _ => unreachable!("Not all valid CLI options were handled"),
     }
 }
 
-fn main() -> Result<()> {
-    let mode = parse_cli()?;
-    let solution = SolutionProvider.get_answer(mode)?;
+fn main() -> Result<ExitCode> {
+    let matches = cli().get_matches();
+    let mut options = parse_options(&matches)?;
 
-    let mut model = Model::new(&solution);
+    // Put the terminal back however turdle exits
+    screen::install_handlers();
 
-    // Init terminal
-    model.terminal.enter_alternate_screen()?;
-    model.terminal.enable_raw_mode()?;
+    // Non-interactive subcommands
+    if let Some(("check", args)) = matches.subcommand() {
+        let answer = args
+            .get_one::<String>("answer")
+            .expect("Answer should be provided");
+        let guesses: Vec<String> = args
+            .get_many::<String>("GUESS")
+            .unwrap_or_default()
+            .cloned()
+            .collect();
+        return tools::check(answer, &guesses, options.share_format);
+    }
 
-    // Main loop
-    model.run()?;
+    if let Some(("search", args)) = matches.subcommand() {
+        let values = |id: &str| -> Vec<String> {
+            args.get_many::<String>(id)
+                .unwrap_or_default()
+                .cloned()
+                .collect()
+        };
+
+        let mut constraints = Constraints::default();
+        if let Some(pattern) = args.get_one::<String>("PATTERN") {
+            constraints = constraints.with_pattern(pattern)?;
+        }
+        for letters in values("has") {
+            constraints = constraints.with_has(&letters);
+        }
+        for letters in values("lacks") {
+            constraints = constraints.with_lacks(&letters);
+        }
+        for spec in values("not-at") {
+            constraints = constraints.with_not_at(&spec)?;
+        }
+        for spec in values("guess") {
+            constraints = constraints.with_guess(&spec)?;
+        }
+
+        return tools::search(
+            &constraints,
+            args.get_flag("words"),
+            args.get_one::<String>("sort").is_some_and(|s| s == "freq"),
+            args.get_one::<usize>("limit").copied(),
+        );
+    }
+
+    if let Some(("challenge", args)) = matches.subcommand() {
+        if let Some(("create", create)) = args.subcommand() {
+            let word = create
+                .get_one::<String>("WORD")
+                .expect("Word should be provided");
+            println!("{}", challenge::create(word)?);
+            return Ok(ExitCode::SUCCESS);
+        }
+    }
+
+    if let Some(("serve", args)) = matches.subcommand() {
+        let bind = args
+            .get_one::<String>("bind")
+            .expect("Bind has a default value");
+        let port = args
+            .get_one::<u16>("port")
+            .expect("Port has a default value");
+
+        // The seed is shared so variants make the same choices for everyone
+        let seed = options.seed.unwrap_or_else(rand::random);
+        let solution = SolutionProvider::default()
+            .with_seed(Some(seed))
+            .get_answer(AppMode::Random)?;
+        lan::serve(
+            &format!("{}:{}", bind, port),
+            lan::Game {
+                solution,
+                variant: options.variant,
+                max_guesses: options.max_guesses,
+                seed,
+            },
+        )?;
+        return Ok(ExitCode::SUCCESS);
+    }
+
+    if let Some(("join", args)) = matches.subcommand() {
+        if options.plain {
+            bail!("--plain can't be used for LAN races");
+        }
+        let addr = args
+            .get_one::<String>("ADDR")
+            .expect("Address should be provided");
+        let name = args
+            .get_one::<String>("name")
+            .cloned()
+            .or_else(|| std::env::var("USER").ok())
+            .unwrap_or_else(|| "Player".into());
+        options.config = config::load()?;
+        return join(addr, &name, options);
+    }
+
+    if let Some(("bot", args)) = matches.subcommand() {
+        let all = args
+            .get_one::<String>("answers")
+            .is_some_and(|a| a == "all");
+        return Ok(bot::run(all, &options)?.exit_code());
+    }
+
+    if let Some(("stats", _)) = matches.subcommand() {
+        stats::summary()?;
+        return Ok(ExitCode::SUCCESS);
+    }
+
+    let mode = parse_mode(&matches)?;
+    if options.plain && matches!(mode, AppMode::Marathon(_) | AppMode::Hotseat(..)) {
+        bail!("--plain can only be used for single games");
+    }
+    if !options.plain {
+        options.config = config::load()?;
+    }
+    match mode {
+        AppMode::Marathon(limit) => marathon(limit, options),
+        AppMode::Hotseat(names, style) => hotseat(names, style, options),
+        mode => play(mode, options),
+    }
+}
+
+// Numeric seeds are used as is, anything else is hashed
+fn parse_seed(s: &str) -> Result<u64> {
+    if let Ok(seed) = s.parse() {
+        return Ok(seed);
+    }
+
+    // FNV-1a, which unlike the standard library's hasher is stable between releases
+    let hash = s.bytes().fold(0xcbf2_9ce4_8422_2325_u64, |hash, b| {
+        (hash ^ b as u64).wrapping_mul(0x0100_0000_01b3)
+    });
+    Ok(hash)
+}
+
+// Parses a time such as "90", "90s", "2m" or "1m30s"
+fn parse_time(s: &str) -> Result<Duration> {
+    let mut secs = 0;
+    let mut num = String::new();
+    for ch in s.trim().chars() {
+        match ch {
+            '0'..='9' => num.push(ch),
+            'm' | 's' if !num.is_empty() => {
+                let n: u64 = num.parse()?;
+                secs += if ch == 'm' { n * 60 } else { n };
+                num.clear();
+            }
+            _ => bail!("Invalid time \"{}\" (use e.g. 90s, 2m or 1m30s)", s),
+        }
+    }
+    if !num.is_empty() {
+        secs += num.parse::<u64>()?;
+    }
+
+    if secs == 0 {
+        bail!("Time must be more than zero");
+    }
+    Ok(Duration::from_secs(secs))
+}
+
+fn play(mode: AppMode, mut options: Options) -> Result<ExitCode> {
+    let board_mode = match mode {
+        AppMode::Assist => BoardMode::Assist,
+        AppMode::Reverse => BoardMode::Reverse,
+        _ => BoardMode::Play,
+    };
+    check_variant(&mode, board_mode, &options)?;
+    // An unseeded random game can carry on from any crashed one, as its word is new anyway
+    let any_puzzle = matches!(mode, AppMode::Random) && options.seed.is_none();
+    let mut solution = SolutionProvider::default()
+        .with_seed(options.seed)
+        .get_answer(mode)?;
+
+    if options.plain {
+        let started = Instant::now();
+        let (result_grid, progress) = plain::play(&solution, &options)?;
+        let outcome = Outcome::of(result_grid.as_ref());
+        report_game(result_grid, progress, solution, &options, started, outcome)?;
+        return Ok(outcome.exit_code());
+    }
+
+    check_board_output(&options)?;
+
+    // Only untimed classic games replay exactly as they were played
+    if board_mode == BoardMode::Play && options.variant == Variant::Classic && !options.timed {
+        if let Some(saved) = screen::resume(&solution, options.max_guesses, any_puzzle)? {
+            solution = saved.solution;
+            options.resume = saved.guesses;
+        }
+    }
+
+    let mut model = Model::new(&solution, &options, board_mode);
+    let started = Instant::now();
+    run_game(&mut model)?;
+
+    let outcome = Outcome::of(model.result_grid.as_ref());
 
-    // Restore terminal
-    model.terminal.leave_alternate_screen()?;
-    model.terminal.disable_raw_mode()?;
+    // Assist and reverse games have no result of their own, so only the exit code is reported
+    if board_mode != BoardMode::Play {
+        return Ok(outcome.exit_code());
+    }
+
+    report_game(
+        model.result_grid,
+        model.progress,
+        solution,
+        &options,
+        started,
+        outcome,
+    )?;
+    Ok(outcome.exit_code())
+}
 
-    // Show results if player got the word
-    if let Some(rg) = model.result_grid {
-        output_result(rg)?;
+// Records a finished game and prints the result
+fn report_game(
+    result_grid: Option<ResultGrid>,
+    progress: Option<ResultGrid>, // Latest state of an unfinished game
+    solution: Solution,
+    options: &Options,
+    started: Instant,
+    outcome: Outcome,
+) -> Result<()> {
+    if let Some(rg) = &result_grid {
+        stats::record_game(rg, outcome);
+    }
+
+    if options.output == OutputFormat::Json {
+        let rg = result_grid
+            .clone()
+            .or(progress.clone())
+            .unwrap_or_else(|| ResultGrid {
+                solution: solution.clone(),
+                variant: options.variant,
+                lies: vec![],
+                max_guesses: options.max_guesses,
+                solved: false,
+                lines_used: 0,
+                guesses: vec![],
+                players: vec![],
+                winner: None,
+                grid: vec![],
+                duration: started.elapsed(),
+                time: None,
+                splits: vec![],
+            });
+        // Keep stdout clean for the report
+        if write_report(share::game_report(&rg, outcome), options)? {
+            return Ok(());
+        }
+    }
+
+    // Show results if the game was finished
+    if let Some(rg) = result_grid {
+        output_result(&rg, options.share_format)?;
+
+        if !rg.solved && !is_shared(&solution, options) && options.share_format != ShareFormat::Json
+        {
+            print_out(&format!("\nThe solution was: {}", reveal(&rg.solution)))?;
+        }
+
+        if options.copy {
+            copy_result(&rg, options.share_format)?;
+        }
     } else {
-        println!("The solution was: \"{}\"", &solution.answer);
+        let shared = is_shared(&solution, options);
+        let solution = progress.map_or(solution, |rg| rg.solution);
+        if !solution.answer.is_empty() && !shared {
+            print_out(&format!("The solution was: {}", reveal(&solution)))?;
+        }
     }
 
     Ok(())
 }
 
-fn output_result(rg: ResultGrid) -> Result<()> {
-    if let Some(num) = rg.solution.wordle_number {
-        // Insert thousands separator
-        let mut wn = num.to_string();
-        if wn.len() > 3 {
-            wn.insert(wn.len() - 3, ',');
+// Only unseeded random and hosted answers are revealed, as daily, seeded and challenge
+// puzzles may be being played by others
+fn is_shared(solution: &Solution, options: &Options) -> bool {
+    solution.wordle_number.is_some() || solution.challenge.is_some() || options.seed.is_some()
+}
+
+// The answer and, for hosted games, who chose it
+fn reveal(solution: &Solution) -> String {
+    match &solution.setter {
+        Some(setter) => format!("\"{}\", set by {}", solution.answer, setter),
+        None => format!("\"{}\"", solution.answer),
+    }
+}
+
+// Races the other players of a `turdle serve` game on the same word
+fn join(addr: &str, name: &str, options: Options) -> Result<ExitCode> {
+    let (game, lan) = lan::connect(addr, name)?;
+    let opponents = Arc::clone(&lan.opponents);
+    let options = Options {
+        variant: game.variant,
+        max_guesses: game.max_guesses,
+        seed: Some(game.seed),
+        ..options
+    };
+
+    check_board_output(&options)?;
+    let mut model = Model::new(&game.solution, &options, BoardMode::Play).with_lan(lan);
+    let started = Instant::now();
+    run_game(&mut model)?;
+
+    let outcome = Outcome::of(model.result_grid.as_ref());
+    report_game(
+        model.result_grid,
+        model.progress,
+        game.solution,
+        &options,
+        started,
+        outcome,
+    )?;
+
+    // How everyone else got on
+    let opponents = opponents.read().unwrap();
+    if !options.json_to_stdout() && !opponents.players.is_empty() {
+        print_out("")?;
+        for (name, opponent) in &opponents.players {
+            let result = match opponent.grid.len() {
+                n if opponent.solved => format!("solved in {}", n),
+                _ if opponent.finished => "missed it".to_string(),
+                n if opponent.left => format!("left after {}", n),
+                n => format!("still playing after {}", n),
+            };
+            print_out(&format!("{}: {}", name, result))?;
         }
+    }
 
-        let heading = format!(
-            "Wordle {} {}/6\n",
-            wn,
-            rg.lines_used
-        );
-        // Print dividing line equal to heading length
-        for _ in 1..heading.len() {
-            execute!(stdout(), Print("─"))?
+    Ok(outcome.exit_code())
+}
+
+fn check_variant(mode: &AppMode, board_mode: BoardMode, options: &Options) -> Result<()> {
+    if options.variant != Variant::Classic && board_mode != BoardMode::Play {
+        bail!("Variants can't be used in assist or reverse mode");
+    }
+    if matches!(options.variant, Variant::Absurdle | Variant::Xordle)
+        && !matches!(mode, AppMode::Random)
+    {
+        bail!("The absurdle and xordle variants pick their own answers, so can only be played in random mode");
+    }
+    if options.plain && board_mode != BoardMode::Play {
+        bail!("--plain can't be used in assist or reverse mode");
+    }
+    // The real game is played elsewhere, so there's no result to report or copy
+    if board_mode != BoardMode::Play
+        && (options.output == OutputFormat::Json || options.output_file.is_some() || options.copy)
+    {
+        bail!("--output json, --output-file and --copy can't be used in assist or reverse mode");
+    }
+    Ok(())
+}
+
+// The board is drawn on stdout, so a piped report would be mixed up with it
+fn check_board_output(options: &Options) -> Result<()> {
+    if options.json_to_stdout() && !io::stdout().is_terminal() {
+        bail!("--output json needs --output-file when stdout isn't a terminal, as the board is drawn there");
+    }
+    Ok(())
+}
+
+// Runs a game in the terminal until the player quits
+fn run_game(model: &mut Model) -> Result<()> {
+    // Init terminal, inline boards are drawn where the cursor is
+    screen::enter(model.inline)?;
+
+    // Main loop
+    model.run()?;
+    leave_game(model)
+}
+
+// Restores the terminal once the last game on screen is over
+fn leave_game(model: &mut Model) -> Result<()> {
+    screen::clear_game();
+    screen::leave()?;
+    if model.inline {
+        model.leave_inline()?;
+    }
+
+    Ok(())
+}
+
+// Plays random words back to back until the time runs out, showing the answer to any missed
+fn marathon(limit: Duration, options: Options) -> Result<ExitCode> {
+    let mut results: Vec<ResultGrid> = vec![];
+    let mut elapsed = Duration::ZERO;
+    let mut model: Option<Model> = None;
+
+    while elapsed < limit {
+        let options = Options {
+            seed: options
+                .seed
+                .map(|seed| seed.wrapping_add(results.len() as u64)),
+            timed: true,
+            time_limit: Some(limit - elapsed),
+            marathon: true,
+            ..options.clone()
+        };
+        let solution = SolutionProvider::default()
+            .with_seed(options.seed)
+            .get_answer(AppMode::Random)?;
+
+        // Words after the first replace the last on the same screen, starting the clock straight away
+        let model = match &mut model {
+            Some(model) => {
+                model.next_word(&solution, &options)?;
+                model.start_clock();
+                model
+            }
+            None => {
+                screen::enter(options.inline)?;
+                model.insert(Model::new(&solution, &options, BoardMode::Play))
+            }
+        };
+        model.run()?;
+
+        let Some(rg) = model.result_grid.take() else {
+            break; // Quit mid-game
+        };
+        elapsed += rg.time.unwrap_or_default();
+        results.push(rg);
+
+        if !model.next_game {
+            break;
         }
-        println!();
-        println!("{}", heading);
+    }
+    if let Some(model) = &mut model {
+        leave_game(model)?;
+    }
+
+    if results.is_empty() {
+        return Ok(Outcome::Quit.exit_code());
+    }
+
+    let solved = results.iter().filter(|rg| rg.solved).count();
+    stats::record_marathon(limit, solved, elapsed, &results);
+
+    let mut text = format!(
+        "Turdle marathon: {} solved in {}\n",
+        solved,
+        format_time(elapsed)
+    );
+    for rg in &results {
+        let score = if rg.solved {
+            rg.lines_used.to_string()
+        } else {
+            "X".to_string()
+        };
+        text.push_str(&format!(
+            "\n{} {}/{} {}",
+            rg.solution.answer.to_ascii_uppercase(),
+            score,
+            rg.max_guesses
+                .map_or("∞".to_string(), |max| max.to_string()),
+            format_time(rg.time.unwrap_or_default()),
+        ));
+    }
+
+    let heading_len = text.lines().next().unwrap_or_default().chars().count();
+    print_out(&"─".repeat(heading_len))?;
+    print_out(&text)?;
+
+    if options.copy && !clipboard::copy(&text)? {
+        eprintln!("(Clipboard not supported by this terminal, copy the result above)");
+    }
+
+    let outcome = if solved > 0 {
+        Outcome::Won
     } else {
-    println!("──────────");
-        println!(
-            "Turdle {}/6\n",
-            rg.lines_used
-        );
+        Outcome::Lost
+    };
+    Ok(outcome.exit_code())
+}
+
+// Plays rounds on a shared board until the players stop, keeping score
+fn hotseat(names: Vec<String>, style: PlayerStyle, options: Options) -> Result<ExitCode> {
+    check_variant(&AppMode::Random, BoardMode::Play, &options)?;
+    if style == PlayerStyle::Race && options.variant == Variant::Absurdle {
+        bail!("The absurdle variant has no answer of its own to race to, so can only be played in turns");
     }
+    check_board_output(&options)?;
 
-    // Print the result emoji grid
-    for line in rg.grid.iter() {
-        if !line.is_empty() {
-            for res in line.iter() {
-                match res {
-                    LetterState::Contains => execute!(stdout(), Print("🟨"))?,
-                    LetterState::Correct => execute!(stdout(), Print("🟩"))?,
-                    _ => execute!(stdout(), Print("⬛"))?,
-                }
-            }
-            execute!(stdout(), Print("\n"))?;
+    let players = Rc::new(RwLock::new(Players::new(names, style)));
+    let count = players.read().unwrap().names.len();
+    let mut results: Vec<ResultGrid> = vec![];
+
+    loop {
+        let round = results.len();
+        let options = Options {
+            seed: options.seed.map(|seed| seed.wrapping_add(round as u64)),
+            // Each player gets the usual number of guesses at their own word
+            max_guesses: match style {
+                PlayerStyle::Race => options.max_guesses.map(|max| max * count),
+                PlayerStyle::Turns => options.max_guesses,
+            },
+            players: Some(Rc::clone(&players)),
+            ..options.clone()
+        };
+        let solution = SolutionProvider::default()
+            .with_seed(options.seed)
+            .get_answer(AppMode::Random)?;
+
+        // Players take turns to start
+        players.write().unwrap().turn = round % count;
+
+        let mut model = Model::new(&solution, &options, BoardMode::Play);
+        run_game(&mut model)?;
+
+        // Rounds aren't recorded in the stats as they're shared between players
+        let Some(rg) = model.result_grid else {
+            break; // Quit mid-round
+        };
+        results.push(rg);
+
+        if !model.next_game {
+            break;
         }
     }
 
+    if results.is_empty() {
+        return Ok(Outcome::Quit.exit_code());
+    }
+
+    let players = players.read().unwrap();
+    if options.output == OutputFormat::Json
+        && write_report(share::hotseat_report(&players, &results), &options)?
+    {
+        return Ok(ExitCode::SUCCESS);
+    }
+
+    let scores: Vec<String> = players
+        .names
+        .iter()
+        .zip(&players.scores)
+        .map(|(name, score)| format!("{} {}", name, score))
+        .collect();
+    let mut text = format!("Turdle hot-seat: {}\n", scores.join(" - "));
+    for rg in &results {
+        let winner = match rg.winner {
+            Some(winner) => format!("{} in {}", players.names[winner], rg.lines_used),
+            None => "unsolved".to_string(),
+        };
+        text.push_str(&format!(
+            "\n{} {}",
+            rg.solution.answer.to_ascii_uppercase(),
+            winner
+        ));
+    }
+
+    let heading_len = text.lines().next().unwrap_or_default().chars().count();
+    print_out(&"─".repeat(heading_len))?;
+    print_out(&text)?;
+
+    if options.copy && !clipboard::copy(&text)? {
+        eprintln!("(Clipboard not supported by this terminal, copy the result above)");
+    }
+
+    Ok(ExitCode::SUCCESS)
+}
+
+// Writes a JSON report to the output file, or to stdout in which case nothing else should be printed
+fn write_report(report: String, options: &Options) -> Result<bool> {
+    match &options.output_file {
+        Some(path) => {
+            fs::write(path, report + "\n")?;
+            Ok(false)
+        }
+        None => {
+            print_out(&report)?;
+            Ok(true)
+        }
+    }
+}
+
+fn copy_result(rg: &ResultGrid, format: ShareFormat) -> Result<()> {
+    let text = share::share_text(rg, format);
+    if clipboard::copy(&text)? {
+        eprintln!("(Result copied to clipboard)");
+    } else {
+        eprintln!("(Clipboard not supported by this terminal, copy the result above)");
+    }
+
     Ok(())
 }
+
+fn output_result(rg: &ResultGrid, format: ShareFormat) -> Result<()> {
+    let text = share::share_text(rg, format);
+
+    if format == ShareFormat::Json {
+        return print_out(&text);
+    }
+
+    // Print dividing line equal to heading length
+    let heading_len = text.lines().next().unwrap_or_default().chars().count();
+    print_out(&"─".repeat(heading_len.max(10)))?;
+    print_out(&text)
+}
+
+// Prints a line of the results, stopping quietly if stdout has been closed, e.g. when piped
+// into `head`
+fn print_out(line: &str) -> Result<()> {
+    match writeln!(io::stdout().lock(), "{}", line) {
+        Err(e) if e.kind() == ErrorKind::BrokenPipe => Ok(()),
+        result => Ok(result?),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_times() {
+        assert_eq!(parse_time("90").unwrap(), Duration::from_secs(90));
+        assert_eq!(parse_time("90s").unwrap(), Duration::from_secs(90));
+        assert_eq!(parse_time("2m").unwrap(), Duration::from_secs(120));
+        assert_eq!(parse_time(" 1m30s ").unwrap(), Duration::from_secs(90));
+        assert_eq!(parse_time("1m30").unwrap(), Duration::from_secs(90));
+
+        assert!(parse_time("0").is_err());
+        assert!(parse_time("").is_err());
+        assert!(parse_time("m").is_err());
+        assert!(parse_time("2h").is_err());
+        assert!(parse_time("-5s").is_err());
+    }
+
+    #[test]
+    fn parses_seeds() {
+        assert_eq!(parse_seed("42").unwrap(), 42);
+
+        // Words hash the same way every time, so shared seeds give the same game
+        assert_eq!(parse_seed("").unwrap(), 0xcbf2_9ce4_8422_2325);
+        assert_eq!(parse_seed("a").unwrap(), 0xaf63_dc4c_8601_ec8c);
+        assert_ne!(
+            parse_seed("monday").unwrap(),
+            parse_seed("tuesday").unwrap()
+        );
+    }
+}
@@ -1,51 +1,106 @@
-use std::time::Duration;
+use std::{
+    io,
+    rc::Rc,
+    sync::{Arc, RwLock},
+    time::Duration,
+};
 
 use anyhow::{bail, Result};
-use tui_realm_stdlib::Phantom as GlobalListener;
+use tui_realm_stdlib::{Label as StatusLine, Phantom as GlobalListener};
 use tuirealm::{
-    props::Style,
-    terminal::TerminalBridge,
+    props::{Alignment, Style},
     tui::{
+        backend::CrosstermBackend,
         buffer::Buffer,
-        layout::{Constraint, Layout},
+        layout::{Constraint, Layout, Rect},
         style::Stylize,
+        TerminalOptions, Viewport,
     },
-    Application, EventListenerCfg, NoUserEvent, PollStrategy, Sub, SubClause, SubEventClause,
-    Update,
+    Application, AttrValue, Attribute, EventListenerCfg, PollStrategy, Sub, SubClause,
+    SubEventClause, Terminal, Update,
 };
 
 use crate::{
-    comp::{board::Board, letter_pool::LetterPool},
+    clipboard,
+    comp::{
+        board::{Board, BoardMode, CellSize, CELL_SIZE_ATTR},
+        candidates::Candidates,
+        help::Help,
+        letter_pool::LetterPool,
+        opponents::{OpponentBoards, OPPONENTS_WIDTH},
+        players::{PlayerStyle, Players, TurnIndicator},
+        timer::{Clock, Timer},
+    },
+    engine::Engine,
+    input::InputListener,
+    keymap::Action,
+    lan::Lan,
     provider::Solution,
-    ResultGrid,
+    screen,
+    share::{self, ShareFormat},
+    theme, Options, ResultGrid,
 };
 
-const TERM_REQ_WIDTH: u16 = 55;
-const TERM_REQ_HEIGHT: u16 = 34;
+const POOL_WIDTH: u16 = 40;
+const POOL_HEIGHT: u16 = 3;
+const CANDIDATES_WIDTH: u16 = 18;
+const CANDIDATES_MARGIN: u16 = 2;
 
 #[derive(Debug, PartialEq)]
 pub enum Msg {
     None,
+    Guessed(ResultGrid, Option<String>), // With any notice about the guess
     Succeded(ResultGrid),
+    Failed(ResultGrid),
+    CopyResult,
+    NextGame,
+    Status(String),
+    ToggleHelp,
     Quit,
 }
 
+// Events from outside tuirealm's own key handling
+#[derive(Debug, PartialEq, Eq, PartialOrd, Clone, Copy)]
+pub enum UserEvent {
+    Click { column: u16, row: u16 }, // Left mouse button pressed
+    Action(Action),                  // Key bound to an action in the keymap
+}
+
 #[derive(Debug, PartialEq, Eq, Clone, Hash)]
 pub enum Id {
     Board,
     LetterPool,
+    Candidates,
+    Status,
+    Timer,
+    TurnIndicator,
+    Opponents,
+    Help,
     GlobalListener,
 }
 
 pub struct Model {
-    pub app: Application<Id, Msg, NoUserEvent>,
+    pub app: Application<Id, Msg, UserEvent>,
     pub quit: bool,
     pub redraw: bool,
-    pub terminal: TerminalBridge,
+    pub terminal: Terminal,
+    pub inline: bool, // Drawn below the cursor instead of on the alternate screen
+    help: bool,
+    help_size: (u16, u16), // Help is centred over the board when it fits
     pub result_grid: Option<ResultGrid>,
+    pub progress: Option<ResultGrid>, // Latest state of an unfinished game
+    share_format: ShareFormat,
+    mode: BoardMode,
+    max_guesses: Option<usize>,
+    clock: Option<Rc<RwLock<Clock>>>, // Set for timed games
+    marathon: bool,
+    players: Option<Rc<RwLock<Players>>>, // Set for hot-seat games
+    lan: Option<Lan>,                     // Set for LAN races
+    pub next_game: bool,                  // Player asked for the next word of a marathon or round
 }
 
-#[derive(Debug, Default, PartialEq, Clone, Copy)]
+// Ordered from least to most informative
+#[derive(Debug, Default, PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Hash)]
 pub enum LetterState {
     #[default]
     Unused,
@@ -56,56 +111,259 @@ pub enum LetterState {
 }
 
 impl Model {
-    pub fn new(solution: &Solution) -> Self {
-        Self {
-            app: Self::init_app(solution).expect("Could not initialise application"),
+    pub fn new(solution: &Solution, options: &Options, mode: BoardMode) -> Self {
+        let clock = (options.timed && mode == BoardMode::Play)
+            .then(|| Rc::new(RwLock::new(Clock::new(options.time_limit))));
+
+        let mut model = Self {
+            app: Self::init_app(solution, options, mode, clock.clone())
+                .expect("Could not initialise application"),
             quit: false,
             redraw: true,
-            terminal: TerminalBridge::new().expect("Could not initialise terminal"),
+            terminal: new_terminal(
+                options
+                    .inline
+                    .then(|| inline_height(options, clock.is_some())),
+            )
+            .expect("Could not initialise terminal"),
+            inline: options.inline,
+            help: false,
+            help_size: Help::new(options.config.keymap.clone()).size(),
             result_grid: None,
+            progress: None,
+            share_format: options.share_format,
+            mode,
+            max_guesses: options.max_guesses,
+            clock,
+            marathon: options.marathon,
+            players: options.players.clone(),
+            lan: None,
+            next_game: false,
+        };
+
+        match mode {
+            BoardMode::Assist => model.set_status("Enter a guess, then mark each letter G, Y or X"),
+            BoardMode::Reverse => {
+                model.set_status("Think of a word, then mark each letter G, Y or X")
+            }
+            BoardMode::Play if model.players.is_some() => model.announce_turn(),
+            BoardMode::Play => {}
         }
+
+        model
+    }
+
+    // Sets up the board for the next word of a marathon, keeping the same screen
+    pub fn next_word(&mut self, solution: &Solution, options: &Options) -> Result<()> {
+        let clock = Rc::new(RwLock::new(Clock::new(options.time_limit)));
+        self.app = Self::init_app(solution, options, self.mode, Some(Rc::clone(&clock)))?;
+        self.clock = Some(clock);
+        self.quit = false;
+        self.redraw = true;
+        self.help = false;
+        self.result_grid = None;
+        self.progress = None;
+        self.next_game = false;
+        Ok(())
+    }
+
+    // Shows the other players of a LAN race next to the board
+    pub fn with_lan(mut self, lan: Lan) -> Self {
+        let opponents = OpponentBoards::new(Arc::clone(&lan.opponents), self.max_guesses);
+        self.app
+            .mount(Id::Opponents, Box::new(opponents), vec![])
+            .expect("Could not mount opponent boards");
+        self.lan = Some(lan);
+        self
     }
 
     pub fn view(&mut self) -> Result<()> {
-        self.terminal.raw_mut().draw(|frame| {
-            let [_, rect_centre, _] = Layout::horizontal([
+        // Candidates and opponents share the space to the right of the board
+        let side_panel = [Id::Candidates, Id::Opponents]
+            .into_iter()
+            .find(|id| self.app.mounted(id));
+        let (panel_margin, panel_width) = match side_panel {
+            Some(Id::Candidates) => (CANDIDATES_MARGIN, CANDIDATES_WIDTH),
+            Some(_) => (CANDIDATES_MARGIN, OPPONENTS_WIDTH),
+            None => (0, 0),
+        };
+
+        // Show every row that fits, scrolling if there are more
+        let rows = self.max_guesses.unwrap_or(usize::MAX) as u16;
+
+        let timer_height = if self.clock.is_some() { 1 } else { 0 };
+        let players_height = if self.players.is_some() { 2 } else { 0 };
+        let other_height = players_height + timer_height + 1 + POOL_HEIGHT;
+        let inline = self.inline;
+        let (help, (help_width, help_height)) = (self.help, self.help_size);
+
+        self.terminal.draw(|frame| {
+            // Shrink the tiles to fit smaller terminals
+            let cell_size = if inline {
+                CellSize::Compact
+            } else {
+                CellSize::fit(
+                    frame
+                        .size()
+                        .width
+                        .saturating_sub(panel_margin + panel_width),
+                    frame.size().height.saturating_sub(other_height),
+                    rows,
+                )
+            };
+            self.app
+                .attr(
+                    &Id::Board,
+                    Attribute::Custom(CELL_SIZE_ATTR),
+                    AttrValue::Number(cell_size as isize),
+                )
+                .expect("Could not set cell size");
+            let board_width = cell_size.board_width();
+            let fit = frame.size().height.saturating_sub(other_height) / cell_size.row_height();
+
+            let [_, rect_centre, _, rect_side, _] = Layout::horizontal([
                 Constraint::Fill(1),
-                Constraint::Length(5 * 11),
+                Constraint::Length(board_width.max(POOL_WIDTH)),
+                Constraint::Length(panel_margin),
+                Constraint::Length(panel_width), // Candidates or opponents
                 Constraint::Fill(1),
             ])
             .areas(frame.size());
 
-            let [_, rect_board, _, rect_letter_pool, _] = Layout::vertical([
+            let [_, rect_players, rect_timer, rect_board, rect_status, rect_letter_pool, _] =
+                Layout::vertical([
+                    Constraint::Fill(1),
+                    Constraint::Length(players_height), // Turn indicator
+                    Constraint::Length(timer_height),   // Timer
+                    Constraint::Length(rows.min(fit).max(1) * cell_size.row_height()), // Board
+                    Constraint::Length(1),              // Margin/status line
+                    Constraint::Length(POOL_HEIGHT),    // Letter pool
+                    Constraint::Fill(1),
+                ])
+                .areas(rect_centre);
+
+            // Board is centred above the letter pool when narrower
+            let [_, rect_board, _] = Layout::horizontal([
                 Constraint::Fill(1),
-                Constraint::Length(6 * 5), // Board
-                Constraint::Length(1),     // Margin
-                Constraint::Length(3),     // Letter pool
+                Constraint::Length(board_width),
                 Constraint::Fill(1),
             ])
-            .areas(rect_centre);
+            .areas(rect_board);
 
             // Render components
             // Check terminal size to avoid panics
-            if terminal_size_ok(frame.buffer_mut()) {
+            let req_width =
+                CellSize::Compact.board_width().max(POOL_WIDTH) + panel_margin + panel_width;
+            let req_height = other_height + CellSize::Compact.row_height();
+            if terminal_size_ok(frame.buffer_mut(), req_width, req_height) {
                 self.app.view(&Id::Board, frame, rect_board);
+                if timer_height > 0 {
+                    self.app.view(&Id::Timer, frame, rect_timer);
+                }
+                if players_height > 0 {
+                    self.app.view(&Id::TurnIndicator, frame, rect_players);
+                }
+                self.app.view(&Id::Status, frame, rect_status);
                 self.app.view(&Id::LetterPool, frame, rect_letter_pool);
+                if let Some(id) = &side_panel {
+                    let rect_panel = Rect {
+                        y: rect_board.y,
+                        height: rect_board.height,
+                        ..rect_side
+                    };
+                    self.app.view(id, frame, rect_panel);
+                }
+            }
+
+            if help {
+                let [_, rect_help, _] = Layout::vertical([
+                    Constraint::Fill(1),
+                    Constraint::Length(help_height),
+                    Constraint::Fill(1),
+                ])
+                .areas(frame.size());
+                let [_, rect_help, _] = Layout::horizontal([
+                    Constraint::Fill(1),
+                    Constraint::Length(help_width),
+                    Constraint::Fill(1),
+                ])
+                .areas(rect_help);
+                self.app.view(&Id::Help, frame, rect_help);
             }
         })?;
 
         Ok(())
     }
 
-    fn init_app(solution: &Solution) -> Result<Application<Id, Msg, NoUserEvent>> {
+    // Moves the cursor below an inline board, leaving it in the scrollback
+    pub fn leave_inline(&mut self) -> Result<()> {
+        let area = self.terminal.get_frame().size();
+        self.terminal
+            .set_cursor(0, area.bottom().saturating_sub(1))?;
+        self.terminal.show_cursor()?;
+        println!();
+        Ok(())
+    }
+
+    fn init_app(
+        solution: &Solution,
+        options: &Options,
+        mode: BoardMode,
+        clock: Option<Rc<RwLock<Clock>>>,
+    ) -> Result<Application<Id, Msg, UserEvent>> {
         let mut app = Application::init(
             EventListenerCfg::default()
-                .default_input_listener(Duration::from_millis(20))
+                .port(
+                    Box::new(InputListener::new(
+                        Duration::from_millis(20),
+                        options.config.keymap.clone(),
+                    )),
+                    Duration::from_millis(20),
+                )
                 .poll_timeout(Duration::from_millis(10))
                 .tick_interval(Duration::from_millis(50)),
         );
 
+        // Race games score each player's guesses against their own answer
+        let engine = match &options.players {
+            Some(players) if players.read().unwrap().style == PlayerStyle::Race => {
+                let count = players.read().unwrap().names.len();
+                Engine::race(options.variant, solution, options.seed, count)
+            }
+            _ => Engine::new(options.variant, solution, options.seed),
+        };
+
         // Mount components
-        let (letter_pool, pool_rc) = LetterPool::new();
-        let board = Board::new(solution).with_letter_state(pool_rc);
+        let (letter_pool, pool_rc) = LetterPool::new(options.config.layouts.clone());
+        let mut board = Board::new(solution)
+            .with_engine(engine)
+            .with_max_guesses(options.max_guesses)
+            .with_letter_state(pool_rc)
+            .with_key_areas(letter_pool.key_areas())
+            .with_mode(mode)
+            .with_guesses(&options.resume);
+
+        if let Some(clock) = clock {
+            board = board.with_clock(Rc::clone(&clock));
+            app.mount(Id::Timer, Box::new(Timer::new(clock)), vec![])?;
+        }
+
+        if let Some(players) = &options.players {
+            board = board.with_players(Rc::clone(players));
+            app.mount(
+                Id::TurnIndicator,
+                Box::new(TurnIndicator::new(Rc::clone(players))),
+                vec![],
+            )?;
+        }
+
+        // Show remaining answers when the player enters the feedback
+        if mode != BoardMode::Play {
+            let (candidates, hints_rc) = Candidates::new();
+            board = board.with_hints(hints_rc);
+            app.mount(Id::Candidates, Box::new(candidates), vec![])?;
+        }
+
         app.mount(
             Id::Board,
             Box::new(board),
@@ -116,6 +374,20 @@ impl Model {
             Box::new(letter_pool),
             vec![Sub::new(SubEventClause::Any, SubClause::Always)],
         )?;
+        app.mount(
+            Id::Status,
+            Box::new(
+                StatusLine::default()
+                    .alignment(Alignment::Center)
+                    .foreground(theme::STATUS_FG),
+            ),
+            vec![],
+        )?;
+        app.mount(
+            Id::Help,
+            Box::new(Help::new(options.config.keymap.clone())),
+            vec![],
+        )?;
         app.mount(Id::GlobalListener, Box::<GlobalListener>::default(), vec![])?;
         app.active(&Id::GlobalListener)?;
 
@@ -155,6 +427,80 @@ impl Model {
 
         Ok(())
     }
+
+    // Start timing without waiting for a keypress
+    pub fn start_clock(&mut self) {
+        if let Some(clock) = &self.clock {
+            clock
+                .write()
+                .expect("Could not get write access to Clock.")
+                .start();
+        }
+    }
+
+    fn is_out_of_time(&self) -> bool {
+        self.clock
+            .as_ref()
+            .is_some_and(|clock| clock.read().unwrap().is_expired())
+    }
+
+    // Keeps the game to save if turdle is killed, except for hot-seat games which aren't recorded
+    fn autosave(&self, rg: &ResultGrid, finished: bool) {
+        if self.mode != BoardMode::Play || self.players.is_some() {
+            return;
+        }
+        // Marathon words are only recorded with the whole marathon, so there's nothing to keep
+        if self.marathon && finished {
+            screen::clear_game();
+        } else {
+            screen::set_game(rg, finished);
+        }
+    }
+
+    // Lets the other players of a LAN race see how the game is going
+    fn send_progress(&mut self, rg: &ResultGrid, finished: bool) {
+        let sent = self.lan.as_ref().map(|lan| lan.send_progress(rg, finished));
+        if let Some(Err(_)) = sent {
+            self.set_status("Lost connection to the server");
+        }
+    }
+
+    fn announce_turn(&mut self) {
+        if let Some(players) = &self.players {
+            let text = format!("{}'s turn", players.read().unwrap().current());
+            self.set_status(&text);
+        }
+    }
+
+    // Scores the round for whoever made the winning guess
+    fn end_round(&mut self, rg: &ResultGrid) {
+        let Some(players) = &self.players else {
+            return;
+        };
+
+        let text = {
+            let mut players = players
+                .write()
+                .expect("Could not get write access to Players.");
+            match rg.winner {
+                Some(winner) => {
+                    players.scores[winner] += 1;
+                    format!(
+                        "{} wins! Press Enter to play again or Esc to finish",
+                        players.names[winner]
+                    )
+                }
+                None => "No winner. Press Enter to play again or Esc to finish".to_string(),
+            }
+        };
+        self.set_status(&text);
+    }
+
+    fn set_status(&mut self, text: &str) {
+        self.app
+            .attr(&Id::Status, Attribute::Text, AttrValue::String(text.into()))
+            .expect("Could not set status line text");
+    }
 }
 
 impl Update<Msg> for Model {
@@ -165,16 +511,102 @@ impl Update<Msg> for Model {
 
             // Handle messages
             match msg {
+                // Esc closes the help before quitting
+                Msg::Quit if self.help => Some(Msg::ToggleHelp),
                 Msg::Quit => {
                     self.quit = true;
                     None
                 }
 
-                Msg::Succeded(rg) => {
+                // Other components stop getting events while the help is shown
+                Msg::ToggleHelp => {
+                    self.help = !self.help;
+                    if self.help {
+                        self.app.lock_subs();
+                    } else {
+                        self.app.unlock_subs();
+                    }
+                    None
+                }
+
+                Msg::Guessed(rg, notice) => {
+                    if let Some(players) = &self.players {
+                        players
+                            .write()
+                            .expect("Could not get write access to Players.")
+                            .next_turn();
+                        self.announce_turn();
+                    }
+                    if let Some(notice) = notice {
+                        self.set_status(&notice);
+                    }
+                    self.send_progress(&rg, false);
+                    self.autosave(&rg, false);
+                    self.progress = Some(rg);
+                    None
+                }
+
+                Msg::Succeded(rg) | Msg::Failed(rg) => {
+                    self.send_progress(&rg, true);
+                    self.autosave(&rg, true);
+                    match self.mode {
+                        BoardMode::Assist => self.set_status("Press Esc to exit"),
+                        BoardMode::Reverse if rg.solved => {
+                            let word = rg.guesses.last().cloned().unwrap_or_default();
+                            self.set_status(&format!(
+                                "Your word is {}! Press Esc to exit",
+                                word.to_ascii_uppercase()
+                            ));
+                        }
+                        BoardMode::Reverse => self.set_status("You win, I couldn't find your word"),
+                        BoardMode::Play if self.players.is_some() => self.end_round(&rg),
+                        BoardMode::Play if self.marathon && rg.solved => self
+                            .set_status("Solved! Press Enter for the next word or Esc to finish"),
+                        BoardMode::Play if self.marathon && self.is_out_of_time() => self
+                            .set_status(&format!(
+                                "Out of time, the word was {}. Press Esc to see your results",
+                                rg.solution.answer.to_ascii_uppercase()
+                            )),
+                        BoardMode::Play if self.marathon => self.set_status(&format!(
+                            "The word was {}. Press Enter for the next word or Esc to finish",
+                            rg.solution.answer.to_ascii_uppercase()
+                        )),
+                        BoardMode::Play if self.is_out_of_time() => self
+                            .set_status("Out of time! Press C to copy your result or Esc to exit"),
+                        BoardMode::Play => {
+                            self.set_status("Press C to copy your result or Esc to exit")
+                        }
+                    }
                     self.result_grid = Some(rg);
                     None
                 }
 
+                Msg::NextGame => {
+                    let finished = self.result_grid.is_some();
+                    let marathon = self.marathon && !self.is_out_of_time();
+                    if finished && (marathon || self.players.is_some()) {
+                        self.next_game = true;
+                        self.quit = true;
+                    }
+                    None
+                }
+
+                Msg::Status(text) => {
+                    self.set_status(&text);
+                    None
+                }
+
+                Msg::CopyResult => {
+                    if let Some(rg) = &self.result_grid {
+                        let text = share::share_text(rg, self.share_format);
+                        match clipboard::copy(&text) {
+                            Ok(true) => self.set_status("Result copied to clipboard"),
+                            _ => self.set_status("Clipboard unavailable, result printed on exit"),
+                        }
+                    }
+                    None
+                }
+
                 _ => None,
             }
         } else {
@@ -183,21 +615,41 @@ impl Update<Msg> for Model {
     }
 }
 
+// Full-screen terminal, or an inline viewport of the given height
+fn new_terminal(inline_height: Option<u16>) -> Result<Terminal> {
+    let backend = CrosstermBackend::new(io::stdout());
+    let terminal = match inline_height {
+        Some(height) => Terminal::with_options(
+            backend,
+            TerminalOptions {
+                viewport: Viewport::Inline(height),
+            },
+        )?,
+        None => Terminal::new(backend)?,
+    };
+    Ok(terminal)
+}
+
+// Room for up to 6 compact rows and everything around the board
+fn inline_height(options: &Options, timed: bool) -> u16 {
+    let rows = options.max_guesses.unwrap_or(usize::MAX).clamp(1, 6) as u16;
+    let timer_height = if timed { 1 } else { 0 };
+    let players_height = if options.players.is_some() { 2 } else { 0 };
+    players_height + timer_height + rows * CellSize::Compact.row_height() + 1 + POOL_HEIGHT
+}
+
 // Returns true if terminal size is large enough to render
 // Otherwise renders text message with size information
-fn terminal_size_ok(buf: &mut Buffer) -> bool {
-    if buf.area.width >= TERM_REQ_WIDTH && buf.area.height >= TERM_REQ_HEIGHT {
+fn terminal_size_ok(buf: &mut Buffer, req_width: u16, req_height: u16) -> bool {
+    if buf.area.width >= req_width && buf.area.height >= req_height {
         true
     } else {
-        let msg = format!(
-            "Terminal too small (min. {}W x {}H)",
-            TERM_REQ_WIDTH, TERM_REQ_HEIGHT
-        );
+        let msg = format!("Terminal too small (min. {}W x {}H)", req_width, req_height);
         buf.set_string(0, 0, msg, Style::default().bold());
 
         if buf.area.height >= 2 {
-            let diff_w = TERM_REQ_WIDTH.saturating_sub(buf.area.width);
-            let diff_h = TERM_REQ_HEIGHT.saturating_sub(buf.area.height);
+            let diff_w = req_width.saturating_sub(buf.area.width);
+            let diff_h = req_height.saturating_sub(buf.area.height);
 
             if diff_w > 0 && diff_h > 0 {
                 let msg = format!("{} more cols & {} more rows needed", diff_w, diff_h);
@@ -0,0 +1,226 @@
+use std::{
+    io::{self, BufRead, IsTerminal, Write},
+    time::{Duration, Instant},
+};
+
+use anyhow::Result;
+use indexmap::IndexMap;
+
+use crate::{
+    engine::{Engine, Variant},
+    model::LetterState,
+    provider::Solution,
+    scoring::{self, WORD_LENGTH},
+    Options, ResultGrid,
+};
+
+// Plays a game a line at a time, for screen readers, dumb terminals and piped input
+// Returns the finished game, or the progress so far if the input ended first
+pub fn play(
+    solution: &Solution,
+    options: &Options,
+) -> Result<(Option<ResultGrid>, Option<ResultGrid>)> {
+    let mut engine = Engine::new(options.variant, solution, options.seed);
+    let mut game = PlainGame {
+        solution: solution.clone(),
+        started: Instant::now(),
+        guesses: vec![],
+        grid: vec![],
+        splits: vec![],
+    };
+    let mut letters: IndexMap<char, LetterState> = IndexMap::new();
+
+    // A JSON report on stdout needs it to itself, so the transcript goes to stderr
+    let mut out: Box<dyn Write> = if options.json_to_stdout() {
+        Box::new(io::stderr())
+    } else {
+        Box::new(io::stdout())
+    };
+
+    match options.max_guesses {
+        Some(1) => writeln!(out, "Guess the {}-letter word in 1 try.", WORD_LENGTH),
+        Some(max) => writeln!(
+            out,
+            "Guess the {}-letter word in {} tries.",
+            WORD_LENGTH, max
+        ),
+        None => writeln!(
+            out,
+            "Guess the {}-letter word, with no limit on tries.",
+            WORD_LENGTH
+        ),
+    }?;
+    if engine.variant() == Variant::Fibble {
+        writeln!(out, "One letter in every row is given the wrong colour.")?;
+    }
+
+    let mut input = io::stdin().lock().lines();
+    while options
+        .max_guesses
+        .is_none_or(|max| game.guesses.len() < max)
+    {
+        write!(out, "Guess {}: ", game.guesses.len() + 1)?;
+        out.flush()?;
+
+        let Some(line) = input.next() else {
+            writeln!(out)?;
+            return Ok((None, Some(game.result_grid(&engine, options, false))));
+        };
+        let guess = line?.trim().to_ascii_lowercase();
+
+        // Echo piped guesses so logs read like a transcript
+        if !io::stdin().is_terminal() {
+            writeln!(out, "{}", guess)?;
+        }
+
+        if guess.chars().count() != WORD_LENGTH || !guess.chars().all(|ch| ch.is_ascii_alphabetic())
+        {
+            writeln!(out, "Guesses must be {} letters.", WORD_LENGTH)?;
+            continue;
+        }
+        if !engine.is_valid(&guess) {
+            writeln!(
+                out,
+                "{} is not in the word list.",
+                guess.to_ascii_uppercase()
+            )?;
+            continue;
+        }
+
+        let states = engine.score(&guess);
+        game.splits.push(game.started.elapsed());
+        writeln!(out, "{}", describe_row(&guess, &states))?;
+        game.guesses.push(guess.clone());
+        game.grid.push(states.clone());
+
+        // Out of time, checked once the guess is in as there's no timer to interrupt input
+        let expired = options
+            .time_limit
+            .is_some_and(|limit| game.started.elapsed() >= limit);
+
+        if engine.is_solved(&guess) {
+            writeln!(out, "Solved in {}!", game.guesses.len())?;
+            return Ok((Some(game.result_grid(&engine, options, true)), None));
+        }
+        if expired {
+            writeln!(out, "Out of time!")?;
+            return Ok((Some(game.result_grid(&engine, options, false)), None));
+        }
+
+        // Colours can't be trusted in Fibble, so only the letters tried are tracked
+        if engine.variant() != Variant::Fibble {
+            for (ch, state) in scoring::letter_summary(&guess, &states) {
+                let best = letters
+                    .get(&ch)
+                    .map_or(state, |current| (*current).max(state));
+                letters.insert(ch, best);
+            }
+        } else {
+            for ch in guess.chars() {
+                letters.entry(ch).or_insert(LetterState::Entered);
+            }
+        }
+        writeln!(out, "{}", describe_letters(&letters))?;
+    }
+
+    writeln!(out, "Out of guesses.")?;
+    Ok((Some(game.result_grid(&engine, options, false)), None))
+}
+
+struct PlainGame {
+    solution: Solution,
+    started: Instant,
+    guesses: Vec<String>,
+    grid: Vec<Vec<LetterState>>,
+    splits: Vec<Duration>,
+}
+
+impl PlainGame {
+    fn result_grid(&self, engine: &Engine, options: &Options, solved: bool) -> ResultGrid {
+        ResultGrid {
+            solution: Solution {
+                answer: engine.reveal(),
+                ..self.solution.clone()
+            },
+            variant: engine.variant(),
+            lies: engine.lies(),
+            max_guesses: options.max_guesses,
+            solved,
+            lines_used: self.guesses.len(),
+            guesses: self.guesses.clone(),
+            players: vec![],
+            winner: None,
+            grid: self.grid.clone(),
+            duration: self.started.elapsed(),
+            time: options.timed.then(|| self.started.elapsed()),
+            splits: if options.timed {
+                self.splits.clone()
+            } else {
+                vec![]
+            },
+        }
+    }
+}
+
+// E.g. "C(green) R(grey) A(yellow) N(grey) E(green)"
+fn describe_row(guess: &str, states: &[LetterState]) -> String {
+    let tiles: Vec<String> = guess
+        .chars()
+        .zip(states)
+        .map(|(ch, state)| format!("{}({})", ch.to_ascii_uppercase(), colour_name(*state)))
+        .collect();
+    tiles.join(" ")
+}
+
+// Spells out what's known about each letter, e.g.
+// "Correct place: E. Wrong place: A. Not in the word: C, N, R. Remaining letters: B, D, ..."
+fn describe_letters(letters: &IndexMap<char, LetterState>) -> String {
+    let list = |wanted: &[LetterState]| -> String {
+        let found: Vec<String> = ('a'..='z')
+            .filter(|ch| wanted.contains(&letters.get(ch).copied().unwrap_or_default()))
+            .map(|ch| ch.to_ascii_uppercase().to_string())
+            .collect();
+        if found.is_empty() {
+            "none".to_string()
+        } else {
+            found.join(", ")
+        }
+    };
+
+    let mut text = String::new();
+    if letters
+        .values()
+        .any(|state| *state > LetterState::Incorrect)
+    {
+        text.push_str(&format!(
+            "Correct place: {}. Wrong place: {}. ",
+            list(&[LetterState::Correct]),
+            list(&[LetterState::Contains]),
+        ));
+    }
+    if letters
+        .values()
+        .any(|state| *state == LetterState::Incorrect)
+    {
+        text.push_str(&format!(
+            "Not in the word: {}. ",
+            list(&[LetterState::Incorrect])
+        ));
+    }
+    if letters.values().any(|state| *state == LetterState::Entered) {
+        text.push_str(&format!("Tried: {}. ", list(&[LetterState::Entered])));
+    }
+    text.push_str(&format!(
+        "Remaining letters: {}.",
+        list(&[LetterState::Unused])
+    ));
+    text
+}
+
+fn colour_name(state: LetterState) -> &'static str {
+    match state {
+        LetterState::Correct => "green",
+        LetterState::Contains => "yellow",
+        _ => "grey",
+    }
+}
@@ -1,31 +1,61 @@
 use std::{
-    io::{self, Write},
+    io::{self, IsTerminal, Write},
     thread::sleep,
     time::Duration,
 };
 
 use anyhow::{bail, Result};
-use rand::Rng;
+use crossterm::{
+    cursor::MoveTo,
+    event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers},
+    execute,
+    terminal::{self, Clear, ClearType},
+};
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
 use serde_json::Value;
 use time::{Month, OffsetDateTime};
 
-use crate::{data::answers::ANSWERS, AppMode};
+use crate::{challenge, data::answers::ANSWERS, scoring, AppMode};
 
 const REQ_TIMEOUT: u64 = 10;
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Default, Clone, PartialEq)]
 pub struct Solution {
     pub wordle_number: Option<u64>,
     pub answer: String,
+    pub challenge: Option<String>, // Code the answer was shared with
+    pub setter: Option<String>,    // Who chose the answer in a hosted game
 }
 
 #[derive(Default)]
-pub struct SolutionProvider;
+pub struct SolutionProvider {
+    seed: Option<u64>,
+}
 
 impl SolutionProvider {
+    // Picks the same random answer every time for a given seed
+    pub fn with_seed(mut self, seed: Option<u64>) -> Self {
+        self.seed = seed;
+        self
+    }
+
     pub fn get_answer(&self, mode: AppMode) -> Result<Solution> {
         match mode {
-            AppMode::Random => Ok(random_answer()),
+            AppMode::Random | AppMode::Marathon(_) | AppMode::Hotseat(..) => {
+                Ok(random_answer(self.seed))
+            }
+            AppMode::Challenge(code) => Ok(Solution {
+                answer: challenge::decode(&code)?,
+                challenge: Some(code.to_ascii_uppercase()),
+                ..Default::default()
+            }),
+            AppMode::Host(name) => Ok(Solution {
+                answer: read_secret_word()?,
+                setter: Some(name.unwrap_or_else(|| "the host".into())),
+                ..Default::default()
+            }),
+            AppMode::Assist | AppMode::Reverse => Ok(Solution::default()), // Answer is unknown
             AppMode::Today(date) | AppMode::Date(date) => self.get_answer_for_date(date),
         }
     }
@@ -41,8 +71,8 @@ impl SolutionProvider {
         }
 
         let (year, month, day) = date.to_calendar_date();
-        print!("Retrieving solution for {} {} {} ... ", day, month, year);
-        io::stdout().flush()?;
+        eprint!("Retrieving solution for {} {} {} ... ", day, month, year);
+        io::stderr().flush()?;
 
         let month = u8::from(month);
         let url = format!(
@@ -69,19 +99,23 @@ impl SolutionProvider {
             .as_str()
             .expect("Could not retrieve solution from NYT API");
 
-        println!("OK"); // Was able to retrieve solution
+        eprintln!("OK"); // Was able to retrieve solution
         sleep(Duration::from_secs(1)); // Delay so output is readable
 
         Ok(Solution {
             wordle_number: Some(wordle_number),
             answer: answer.into(),
+            ..Default::default()
         })
     }
 }
 
-fn random_answer() -> Solution {
+fn random_answer(seed: Option<u64>) -> Solution {
     let answers = ANSWERS.lines().collect::<Vec<_>>();
-    let idx = rand::thread_rng().gen_range(0..answers.len());
+    let idx = match seed {
+        Some(seed) => ChaCha8Rng::seed_from_u64(seed).gen_range(0..answers.len()),
+        None => rand::thread_rng().gen_range(0..answers.len()),
+    };
     let answer = *answers
         .get(idx)
         .expect("Could not get random answer at index to start game");
@@ -89,5 +123,66 @@ fn random_answer() -> Solution {
     Solution {
         wordle_number: None,
         answer: answer.into(),
+        ..Default::default()
+    }
+}
+
+// Asks the host for the answer without showing it, then clears the screen for the player
+fn read_secret_word() -> Result<String> {
+    loop {
+        eprint!("Enter the word to be guessed (hidden): ");
+        io::stderr().flush()?;
+
+        let word = if io::stdin().is_terminal() {
+            terminal::enable_raw_mode()?;
+            let word = read_hidden();
+            terminal::disable_raw_mode()?;
+            eprintln!();
+            word?
+        } else {
+            let mut line = String::new();
+            io::stdin().read_line(&mut line)?;
+            line.trim().to_ascii_lowercase()
+        };
+
+        if scoring::is_valid_word(&word) {
+            execute!(
+                io::stderr(),
+                Clear(ClearType::All),
+                Clear(ClearType::Purge),
+                MoveTo(0, 0)
+            )?;
+            return Ok(word);
+        }
+
+        if !io::stdin().is_terminal() {
+            bail!("That word isn't in the word lists");
+        }
+        eprintln!("That word isn't in the word lists, try again");
+    }
+}
+
+// Reads a line in raw mode, so typed letters aren't echoed
+fn read_hidden() -> Result<String> {
+    let mut word = String::new();
+    loop {
+        if let Event::Key(KeyEvent {
+            code,
+            modifiers,
+            kind: KeyEventKind::Press,
+            ..
+        }) = event::read()?
+        {
+            match code {
+                KeyCode::Enter => return Ok(word),
+                KeyCode::Backspace => _ = word.pop(),
+                KeyCode::Esc => bail!("Cancelled"),
+                KeyCode::Char('c') if modifiers.contains(KeyModifiers::CONTROL) => {
+                    bail!("Cancelled")
+                }
+                KeyCode::Char(ch) if ch.is_ascii_alphabetic() => word.push(ch.to_ascii_lowercase()),
+                _ => {}
+            }
+        }
     }
 }
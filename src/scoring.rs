@@ -0,0 +1,104 @@
+use indexmap::IndexMap;
+
+use crate::{
+    data::{answers::ANSWERS, words::WORDS},
+    model::LetterState,
+};
+
+pub const WORD_LENGTH: usize = 5;
+
+// True if the word is in either word list
+pub fn is_valid_word(word: &str) -> bool {
+    word.len() == WORD_LENGTH
+        && word.chars().all(|c| c.is_ascii_lowercase())
+        && (ANSWERS.contains(word) || WORDS.contains(word))
+}
+
+// Scores a guess against the answer
+// Exact matches are found first, so a letter is only marked as `Contains` if the
+// answer has a copy of it that isn't already accounted for
+pub fn score(guess: &str, answer: &str) -> Vec<LetterState> {
+    let guess_chars = guess.chars().collect::<Vec<_>>();
+    let answer_chars = answer.chars().collect::<Vec<_>>();
+    let mut res = vec![LetterState::Incorrect; guess_chars.len()];
+
+    // Answer letters not matched exactly
+    let mut unmatched = Vec::with_capacity(WORD_LENGTH);
+    for (i, ch) in answer_chars.iter().enumerate() {
+        if guess_chars.get(i) == Some(ch) {
+            res[i] = LetterState::Correct;
+        } else {
+            unmatched.push(*ch);
+        }
+    }
+
+    for (i, ch) in guess_chars.iter().enumerate() {
+        if res[i] == LetterState::Correct {
+            continue;
+        }
+
+        if let Some(idx) = unmatched.iter().position(|c| c == ch) {
+            unmatched.swap_remove(idx);
+            res[i] = LetterState::Contains;
+        }
+    }
+
+    res
+}
+
+// Best state of each letter in a scored guess, for updating the letter pool
+pub fn letter_summary(guess: &str, states: &[LetterState]) -> IndexMap<char, LetterState> {
+    let mut res = IndexMap::new();
+    for (ch, state) in guess.chars().zip(states) {
+        res.entry(ch)
+            .and_modify(|best: &mut LetterState| *best = (*best).max(*state))
+            .or_insert(*state);
+    }
+
+    res
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{share, solver::parse_pattern};
+
+    fn pattern(guess: &str, answer: &str) -> String {
+        share::pattern(&score(guess, answer))
+    }
+
+    #[test]
+    fn scores_exact_and_misplaced_letters() {
+        assert_eq!(pattern("crane", "crane"), "GGGGG");
+        assert_eq!(pattern("crane", "abide"), "..Y.G");
+        assert_eq!(pattern("xylyl", "crane"), ".....");
+    }
+
+    #[test]
+    fn repeated_letters_are_only_marked_once() {
+        assert_eq!(pattern("speed", "abide"), "..Y.Y");
+        assert_eq!(pattern("geese", "those"), "...GG");
+        assert_eq!(pattern("those", "geese"), "...GG");
+        assert_eq!(pattern("eerie", "there"), "Y.Y.G");
+    }
+
+    #[test]
+    fn exact_matches_take_priority_over_earlier_misplaced_letters() {
+        // The first L would be yellow if letters were matched left to right
+        assert_eq!(pattern("lolly", "golly"), ".GGGG");
+        assert_eq!(pattern("allee", "eagle"), "YY.YG");
+    }
+
+    #[test]
+    fn letter_summary_keeps_the_best_state() {
+        let summary = letter_summary("geese", &parse_pattern("...GG").unwrap());
+        assert_eq!(
+            summary.into_iter().collect::<Vec<_>>(),
+            vec![
+                ('g', LetterState::Incorrect),
+                ('e', LetterState::Correct),
+                ('s', LetterState::Correct),
+            ]
+        );
+    }
+}
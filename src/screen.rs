@@ -0,0 +1,199 @@
+use std::{
+    fs,
+    io::{self, IsTerminal, Write},
+    panic,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Mutex, OnceLock,
+    },
+    thread::{self, ThreadId},
+};
+
+use anyhow::Result;
+use crossterm::{
+    cursor::Show,
+    event::{DisableMouseCapture, EnableMouseCapture},
+    execute,
+    terminal::{self, EnterAlternateScreen, LeaveAlternateScreen},
+};
+
+use serde_json::Value;
+
+use crate::{provider::Solution, scoring, share, stats, Outcome, ResultGrid};
+
+const AUTOSAVE_FILE: &str = "autosave.json";
+
+// Terminal state to undo if turdle exits unexpectedly
+static RAW_MODE: AtomicBool = AtomicBool::new(false);
+static ALTERNATE_SCREEN: AtomicBool = AtomicBool::new(false);
+
+// Game being played, saved if turdle crashes or is killed
+// Shared with the panic hook and signal thread, hence a static
+static GAME: Mutex<Option<(ResultGrid, bool)>> = Mutex::new(None);
+
+// Thread drawing the board, the only one whose panic ends the game
+static UI_THREAD: OnceLock<ThreadId> = OnceLock::new();
+
+// Sets up the terminal for the board, leaving the screen alone for inline boards
+pub fn enter(inline: bool) -> Result<()> {
+    if !inline {
+        execute!(io::stdout(), EnterAlternateScreen, EnableMouseCapture)?;
+        ALTERNATE_SCREEN.store(true, Ordering::SeqCst);
+    }
+    terminal::enable_raw_mode()?;
+    RAW_MODE.store(true, Ordering::SeqCst);
+    Ok(())
+}
+
+pub fn leave() -> Result<()> {
+    if RAW_MODE.swap(false, Ordering::SeqCst) {
+        terminal::disable_raw_mode()?;
+    }
+    if ALTERNATE_SCREEN.swap(false, Ordering::SeqCst) {
+        execute!(io::stdout(), LeaveAlternateScreen, DisableMouseCapture)?;
+    }
+    Ok(())
+}
+
+// Latest state of the game, with whether it's finished
+pub fn set_game(rg: &ResultGrid, finished: bool) {
+    *GAME.lock().unwrap() = Some((rg.clone(), finished));
+}
+
+// Game has been reported normally, so there's nothing to save
+pub fn clear_game() {
+    *GAME.lock().unwrap() = None;
+}
+
+// Restores the terminal and saves the game on a panic, SIGTERM or SIGHUP
+// Must be called from the thread that draws the board
+pub fn install_handlers() {
+    let _ = UI_THREAD.set(thread::current().id());
+    let default_hook = panic::take_hook();
+    panic::set_hook(Box::new(move |info| {
+        // Network threads can panic without stopping the board, which still needs the terminal
+        if UI_THREAD.get() == Some(&thread::current().id()) {
+            restore();
+        }
+        default_hook(info);
+    }));
+
+    #[cfg(unix)]
+    {
+        use signal_hook::{
+            consts::{SIGHUP, SIGTERM},
+            iterator::Signals,
+        };
+
+        let Ok(mut signals) = Signals::new([SIGTERM, SIGHUP]) else {
+            return;
+        };
+        std::thread::spawn(move || {
+            if let Some(signal) = signals.forever().next() {
+                restore();
+                std::process::exit(128 + signal);
+            }
+        });
+    }
+}
+
+// Best effort, as the terminal may already be gone
+fn restore() {
+    let _ = leave();
+    let _ = execute!(io::stdout(), Show);
+
+    // The lock may be held by the code that panicked
+    let Ok(mut game) = GAME.try_lock() else {
+        return;
+    };
+    match game.take() {
+        Some((rg, true)) => stats::record_game(&rg, Outcome::of(Some(&rg))),
+        Some((rg, false)) => match autosave(&rg) {
+            Ok(path) => eprintln!("(Game saved to {}, start it again to carry on)", path),
+            Err(e) => eprintln!("(Could not save game: {})", e),
+        },
+        None => {}
+    }
+}
+
+// Saved in the same format as `--output json`
+fn autosave(rg: &ResultGrid) -> Result<String> {
+    let dir = stats::data_dir()?;
+    fs::create_dir_all(&dir)?;
+    let path = dir.join(AUTOSAVE_FILE);
+    fs::write(&path, share::game_report(rg, Outcome::Quit) + "\n")?;
+    Ok(path.display().to_string())
+}
+
+// Unfinished game saved when turdle crashed or was killed
+pub struct SavedGame {
+    pub solution: Solution,
+    pub guesses: Vec<String>,
+}
+
+// Offers to carry on with a saved game of the same puzzle, or of any puzzle for a new random word
+// The save is removed once offered, so a declined game isn't offered again
+pub fn resume(
+    solution: &Solution,
+    max_guesses: Option<usize>,
+    any_puzzle: bool,
+) -> Result<Option<SavedGame>> {
+    let path = stats::data_dir()?.join(AUTOSAVE_FILE);
+    let Ok(saved) = fs::read_to_string(&path) else {
+        return Ok(None);
+    };
+    let Ok(doc) = serde_json::from_str::<Value>(&saved) else {
+        return Ok(None);
+    };
+
+    // Timed, hot-seat and variant games can't be replayed as they were
+    let classic =
+        doc["variant"] == "classic" && doc["time_secs"].is_null() && doc["players"].is_null();
+    let answer = doc["answer"].as_str().unwrap_or_default();
+    let wordle_number = doc["wordle_number"].as_u64();
+    let same_puzzle = answer == solution.answer && wordle_number == solution.wordle_number;
+    let guesses: Vec<String> = doc["guesses"]
+        .as_array()
+        .map(|guesses| {
+            guesses
+                .iter()
+                .filter_map(Value::as_str)
+                .map(String::from)
+                .collect()
+        })
+        .unwrap_or_default();
+
+    if !classic
+        || !(same_puzzle || any_puzzle)
+        || !scoring::is_valid_word(answer)
+        || guesses.is_empty()
+        || !guesses.iter().all(|guess| scoring::is_valid_word(guess))
+        || doc["max_guesses"].as_u64().map(|n| n as usize) != max_guesses
+        || !io::stdin().is_terminal()
+    {
+        return Ok(None);
+    }
+
+    eprint!(
+        "Carry on with your unfinished game ({} guesses so far)? [Y/n] ",
+        guesses.len()
+    );
+    io::stderr().flush()?;
+    let mut reply = String::new();
+    io::stdin().read_line(&mut reply)?;
+    fs::remove_file(&path)?;
+    if reply.trim().eq_ignore_ascii_case("n") {
+        return Ok(None);
+    }
+
+    let solution = if same_puzzle {
+        solution.clone()
+    } else {
+        Solution {
+            answer: answer.to_string(),
+            wordle_number,
+            ..Default::default()
+        }
+    };
+    Ok(Some(SavedGame { solution, guesses }))
+}
@@ -0,0 +1,316 @@
+use std::str::FromStr;
+
+use anyhow::{bail, Error};
+use serde_json::{json, Value};
+
+use crate::{
+    comp::{players::Players, timer::format_time},
+    engine::Variant,
+    model::LetterState,
+    Outcome, ResultGrid,
+};
+
+pub const SHARE_FORMATS: [&str; 6] = ["emoji", "contrast", "ascii", "spoiler", "markdown", "json"];
+
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub enum ShareFormat {
+    #[default]
+    Emoji,
+    Contrast, // Emoji using the high-contrast palette
+    Ascii,
+    Spoiler, // Emoji with Discord spoiler-tagged words
+    Markdown,
+    Json,
+}
+
+impl FromStr for ShareFormat {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "emoji" => Ok(Self::Emoji),
+            "contrast" => Ok(Self::Contrast),
+            "ascii" => Ok(Self::Ascii),
+            "spoiler" => Ok(Self::Spoiler),
+            "markdown" => Ok(Self::Markdown),
+            "json" => Ok(Self::Json),
+            _ => bail!("Unknown share format \"{}\"", s),
+        }
+    }
+}
+
+// Builds the text block to be shared for a finished game
+pub fn share_text(rg: &ResultGrid, format: ShareFormat) -> String {
+    let rows = rg.grid.iter().filter(|line| !line.is_empty());
+
+    match format {
+        ShareFormat::Json => {
+            let grid: Vec<String> = rows.map(|line| pattern(line)).collect();
+            let mut doc = json!({
+                "game": game_name(rg),
+                "wordle_number": rg.solution.wordle_number,
+                "solved": rg.solved,
+                "guesses": rg.lines_used,
+                "max_guesses": rg.max_guesses,
+                "grid": grid,
+            });
+            if let Some(lies) = lie_positions(rg) {
+                doc["lies"] = json!(lies);
+            }
+            add_players(&mut doc, rg);
+            add_times(&mut doc, rg);
+            serde_json::to_string_pretty(&doc).expect("Could not serialise result grid")
+        }
+
+        ShareFormat::Markdown => {
+            let grid: Vec<String> = rows
+                .enumerate()
+                .map(|(i, line)| {
+                    let row = mark_lie(tiles(line, format, rg.variant), rg.lie(i), format);
+                    match rg.players.get(i) {
+                        Some(name) => format!("{} {}", row, name),
+                        None => row,
+                    }
+                })
+                .collect();
+            format!("**{}**\n\n{}", heading(rg), grid.join("  \n"))
+        }
+
+        _ => {
+            let mut text = heading(rg);
+            text.push('\n');
+
+            for (i, line) in rows.enumerate() {
+                text.push('\n');
+                text.push_str(&mark_lie(
+                    tiles(line, format, rg.variant),
+                    rg.lie(i),
+                    format,
+                ));
+                if format == ShareFormat::Spoiler {
+                    if let Some(word) = rg.guesses.get(i) {
+                        text.push_str(&format!(" ||{}||", word.to_ascii_uppercase()));
+                    }
+                }
+                if let Some(name) = rg.players.get(i) {
+                    text.push_str(&format!(" {}", name));
+                }
+            }
+
+            text
+        }
+    }
+}
+
+// Full report of a game, including the answer, for scripts
+pub fn game_report(rg: &ResultGrid, outcome: Outcome) -> String {
+    serde_json::to_string_pretty(&report(rg, outcome)).expect("Could not serialise game report")
+}
+
+// Scores and rounds of a hot-seat game
+pub fn hotseat_report(players: &Players, rounds: &[ResultGrid]) -> String {
+    let scores: Vec<Value> = players
+        .names
+        .iter()
+        .zip(&players.scores)
+        .map(|(name, score)| json!({"name": name, "score": score}))
+        .collect();
+    let rounds: Vec<Value> = rounds
+        .iter()
+        .map(|rg| {
+            let outcome = if rg.solved {
+                Outcome::Won
+            } else {
+                Outcome::Lost
+            };
+            let mut doc = report(rg, outcome);
+            doc["winner"] = json!(rg.winner.map(|winner| &players.names[winner]));
+            doc
+        })
+        .collect();
+
+    let doc = json!({"players": scores, "rounds": rounds});
+    serde_json::to_string_pretty(&doc).expect("Could not serialise hot-seat report")
+}
+
+pub fn report(rg: &ResultGrid, outcome: Outcome) -> Value {
+    let outcome = match outcome {
+        Outcome::Won => "won",
+        Outcome::Lost => "lost",
+        Outcome::Quit => "quit",
+    };
+
+    let mut doc = json!({
+        "answer": rg.solution.answer,
+        "wordle_number": rg.solution.wordle_number,
+        "outcome": outcome,
+        "won": rg.solved,
+        "guesses": rg.guesses,
+        "grid": rg.grid.iter().map(|line| pattern(line)).collect::<Vec<_>>(),
+        "variant": variant_name(rg.variant),
+        "max_guesses": rg.max_guesses,
+        "duration_secs": rg.duration.as_secs_f64(),
+    });
+    if let Some(lies) = lie_positions(rg) {
+        doc["lies"] = json!(lies);
+    }
+    add_players(&mut doc, rg);
+    add_times(&mut doc, rg);
+    doc
+}
+
+// Row of tiles in the style of the share format
+pub fn tiles(line: &[LetterState], format: ShareFormat, variant: Variant) -> String {
+    match format {
+        ShareFormat::Contrast => line.iter().map(|ls| contrast_tile(*ls)).collect(),
+        ShareFormat::Ascii => {
+            let tiles: Vec<String> = line.iter().map(|ls| ascii_tile(*ls).to_string()).collect();
+            tiles.join(" ")
+        }
+        // Absurdle shares with white squares
+        _ if variant == Variant::Absurdle => line.iter().map(|ls| absurdle_tile(*ls)).collect(),
+        _ => line.iter().map(|ls| emoji_tile(*ls)).collect(),
+    }
+}
+
+pub fn variant_name(variant: Variant) -> &'static str {
+    match variant {
+        Variant::Classic => "classic",
+        Variant::Absurdle => "absurdle",
+        Variant::Fibble => "fibble",
+        Variant::Xordle => "xordle",
+    }
+}
+
+// Replaces the tile that lied with a red square, or `!` in ascii
+fn mark_lie(tiles: String, lie: Option<usize>, format: ShareFormat) -> String {
+    let Some(pos) = lie else {
+        return tiles;
+    };
+
+    // Ascii tiles are separated by spaces
+    let (marker, idx) = match format {
+        ShareFormat::Ascii => ('!', pos * 2),
+        _ => ('🟥', pos),
+    };
+    tiles
+        .chars()
+        .enumerate()
+        .map(|(i, ch)| if i == idx { marker } else { ch })
+        .collect()
+}
+
+// Player who made each guess in hot-seat games
+fn add_players(doc: &mut Value, rg: &ResultGrid) {
+    if !rg.players.is_empty() {
+        doc["players"] = json!(rg.players);
+    }
+}
+
+// Timer readings for timed games
+fn add_times(doc: &mut Value, rg: &ResultGrid) {
+    if let Some(time) = rg.time {
+        doc["time_secs"] = json!(time.as_secs_f64());
+        let splits: Vec<f64> = rg.splits.iter().map(|s| s.as_secs_f64()).collect();
+        doc["splits_secs"] = json!(splits);
+    }
+}
+
+// 1-based positions of the lying tiles, or null for rows that told the truth
+fn lie_positions(rg: &ResultGrid) -> Option<Vec<Option<usize>>> {
+    if rg.variant != Variant::Fibble {
+        return None;
+    }
+    Some(rg.lies.iter().map(|lie| lie.map(|pos| pos + 1)).collect())
+}
+
+// Row of tiles as `G`, `Y` and `.` characters, e.g. "GY..G"
+pub fn pattern(line: &[LetterState]) -> String {
+    line.iter().map(|ls| ascii_tile(*ls)).collect()
+}
+
+// E.g. "Wordle 1,035 4/6", "Turdle X/6" or "Turdle 3/6 in 0:42.1" for timed games
+fn heading(rg: &ResultGrid) -> String {
+    let heading = match rg.time {
+        Some(time) => format!("{} in {}", score_heading(rg), format_time(time)),
+        None => score_heading(rg),
+    };
+
+    // Hot-seat winner
+    match rg.players.last() {
+        Some(name) if rg.solved => format!("{}, won by {}", heading, name),
+        _ => heading,
+    }
+}
+
+fn score_heading(rg: &ResultGrid) -> String {
+    let score = if rg.solved {
+        rg.lines_used.to_string()
+    } else {
+        "X".to_string()
+    };
+
+    let limit = rg
+        .max_guesses
+        .map_or("∞".to_string(), |max| max.to_string());
+
+    if rg.variant == Variant::Absurdle {
+        return format!("Absurdle {}/{}", score, limit);
+    }
+
+    if let Some(num) = rg.solution.wordle_number {
+        // Insert thousands separator
+        let mut wn = num.to_string();
+        if wn.len() > 3 {
+            wn.insert(wn.len() - 3, ',');
+        }
+
+        format!("{} {} {}/{}", game_name(rg), wn, score, limit)
+    } else if let Some(code) = &rg.solution.challenge {
+        format!("{} #{} {}/{}", game_name(rg), code, score, limit)
+    } else {
+        format!("{} {}/{}", game_name(rg), score, limit)
+    }
+}
+
+fn game_name(rg: &ResultGrid) -> &'static str {
+    match rg.variant {
+        Variant::Absurdle => "Absurdle",
+        Variant::Fibble => "Fibble",
+        Variant::Xordle => "Xordle",
+        _ if rg.solution.wordle_number.is_some() => "Wordle",
+        _ => "Turdle",
+    }
+}
+
+fn emoji_tile(ls: LetterState) -> &'static str {
+    match ls {
+        LetterState::Contains => "🟨",
+        LetterState::Correct => "🟩",
+        _ => "⬛",
+    }
+}
+
+fn absurdle_tile(ls: LetterState) -> &'static str {
+    match ls {
+        LetterState::Contains => "🟨",
+        LetterState::Correct => "🟩",
+        _ => "⬜",
+    }
+}
+
+fn contrast_tile(ls: LetterState) -> &'static str {
+    match ls {
+        LetterState::Contains => "🟦",
+        LetterState::Correct => "🟧",
+        _ => "⬛",
+    }
+}
+
+fn ascii_tile(ls: LetterState) -> char {
+    match ls {
+        LetterState::Contains => 'Y',
+        LetterState::Correct => 'G',
+        _ => '.',
+    }
+}
@@ -0,0 +1,281 @@
+use anyhow::{bail, Context, Result};
+use indexmap::IndexMap;
+
+use crate::{
+    data::{answers::ANSWERS, words::WORDS},
+    model::LetterState,
+    scoring::{self, WORD_LENGTH},
+};
+
+// Wordle-style constraints on the solution
+#[derive(Debug, Default, Clone)]
+pub struct Constraints {
+    pattern: [Option<char>; WORD_LENGTH],
+    has: Vec<char>,
+    lacks: Vec<char>,
+    not_at: Vec<(char, usize)>,
+    guesses: Vec<(String, Vec<LetterState>)>,
+}
+
+impl Constraints {
+    // Known letters by position, e.g. "cr_n_"
+    pub fn with_pattern(mut self, pattern: &str) -> Result<Self> {
+        let chars = pattern.to_ascii_lowercase().chars().collect::<Vec<_>>();
+        if chars.len() != WORD_LENGTH {
+            bail!("Pattern must be {} characters long", WORD_LENGTH);
+        }
+
+        for (i, ch) in chars.into_iter().enumerate() {
+            self.pattern[i] = match ch {
+                'a'..='z' => Some(ch),
+                '_' | '.' | '?' => None,
+                _ => bail!("Invalid character '{}' in pattern (use a-z or _)", ch),
+            }
+        }
+
+        Ok(self)
+    }
+
+    pub fn with_has(mut self, letters: &str) -> Self {
+        self.has.extend(letters.to_ascii_lowercase().chars());
+        self
+    }
+
+    pub fn with_lacks(mut self, letters: &str) -> Self {
+        self.lacks.extend(letters.to_ascii_lowercase().chars());
+        self
+    }
+
+    // A letter in the word but not at the given position, e.g. "a:2"
+    pub fn with_not_at(mut self, not_at: &str) -> Result<Self> {
+        let (letters, positions) = not_at
+            .split_once(':')
+            .context("Use LETTER:POSITION for letters not at a position, e.g. a:2")?;
+
+        for pos in positions.split(',') {
+            let pos: usize = pos
+                .trim()
+                .parse()
+                .ok()
+                .filter(|p| (1..=WORD_LENGTH).contains(p))
+                .with_context(|| format!("Position must be 1 to {}", WORD_LENGTH))?;
+
+            for ch in letters.to_ascii_lowercase().chars() {
+                self.not_at.push((ch, pos - 1));
+            }
+        }
+
+        self.has.extend(letters.to_ascii_lowercase().chars());
+        Ok(self)
+    }
+
+    // A previous guess and its feedback, e.g. "crane:..Y.G"
+    pub fn with_guess(self, guess: &str) -> Result<Self> {
+        let (word, pattern) = guess
+            .split_once(':')
+            .context("Use WORD:PATTERN for guesses, e.g. crane:..Y.G")?;
+        let word = word.to_ascii_lowercase();
+        let states = parse_pattern(pattern)?;
+        if word.len() != WORD_LENGTH {
+            bail!("Guess \"{}\" must be {} letters long", word, WORD_LENGTH);
+        }
+
+        Ok(self.with_feedback(&word, states))
+    }
+
+    pub fn with_feedback(mut self, word: &str, states: Vec<LetterState>) -> Self {
+        self.guesses.push((word.to_string(), states));
+        self
+    }
+
+    pub fn matches(&self, word: &str) -> bool {
+        let chars = word.chars().collect::<Vec<_>>();
+
+        self.pattern
+            .iter()
+            .zip(&chars)
+            .all(|(p, ch)| p.is_none_or(|p| p == *ch))
+            && self.has.iter().all(|ch| chars.contains(ch))
+            && !self.lacks.iter().any(|ch| chars.contains(ch))
+            && !self.not_at.iter().any(|(ch, pos)| chars.get(*pos) == Some(ch))
+            // A candidate must produce the same feedback for every guess
+            && self
+                .guesses
+                .iter()
+                .all(|(guess, states)| scoring::score(guess, word) == *states)
+    }
+}
+
+// Parses feedback written as `G`, `Y` and `.` characters, e.g. "..Y.G"
+pub fn parse_pattern(pattern: &str) -> Result<Vec<LetterState>> {
+    let states = pattern
+        .chars()
+        .map(|ch| match ch.to_ascii_lowercase() {
+            'g' => Ok(LetterState::Correct),
+            'y' => Ok(LetterState::Contains),
+            '.' | '_' | '-' | 'x' | 'b' => Ok(LetterState::Incorrect),
+            _ => bail!("Invalid character '{}' in feedback (use G, Y or .)", ch),
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    if states.len() != WORD_LENGTH {
+        bail!("Feedback must be {} characters long", WORD_LENGTH);
+    }
+
+    Ok(states)
+}
+
+pub fn answers() -> Vec<&'static str> {
+    ANSWERS.lines().collect()
+}
+
+// Answers and other valid guesses, in alphabetical order
+pub fn all_words() -> Vec<&'static str> {
+    let mut words = ANSWERS.lines().chain(WORDS.lines()).collect::<Vec<_>>();
+    words.sort_unstable();
+    words
+}
+
+pub fn candidates<'a>(pool: &[&'a str], constraints: &Constraints) -> Vec<&'a str> {
+    pool.iter()
+        .copied()
+        .filter(|word| constraints.matches(word))
+        .collect()
+}
+
+// Orders words by how common their distinct letters are among the words themselves
+pub fn sort_by_frequency(words: &mut [&str]) {
+    let mut freq: IndexMap<char, usize> = IndexMap::new();
+    for word in words.iter() {
+        for ch in distinct_letters(word) {
+            *freq.entry(ch).or_default() += 1;
+        }
+    }
+
+    let word_score = |word: &str| -> usize {
+        distinct_letters(word)
+            .iter()
+            .map(|ch| freq.get(ch).copied().unwrap_or_default())
+            .sum()
+    };
+    words.sort_by_cached_key(|word| std::cmp::Reverse(word_score(word)));
+}
+
+fn distinct_letters(word: &str) -> Vec<char> {
+    let mut letters = word.chars().collect::<Vec<_>>();
+    letters.sort_unstable();
+    letters.dedup();
+    letters
+}
+
+// Best opening guesses as ranked by `suggest`, to avoid scoring every answer against every other
+const OPENERS: [&str; 8] = [
+    "raise", "arise", "irate", "arose", "alter", "saner", "later", "snare",
+];
+
+// Guesses from the answer list expected to leave the fewest candidates, best first
+pub fn suggest(candidates: &[&'static str], count: usize) -> Vec<&'static str> {
+    if candidates.len() <= 2 {
+        return candidates.iter().take(count).copied().collect();
+    }
+
+    let pool = answers();
+    if candidates.len() == pool.len() {
+        return OPENERS.iter().take(count).copied().collect();
+    }
+
+    let mut ranked = pool
+        .into_iter()
+        .map(|guess| {
+            let mut buckets = [0_usize; 243]; // One for each possible pattern
+            for answer in candidates {
+                buckets[pattern_code(guess.as_bytes(), answer.as_bytes())] += 1;
+            }
+            let expected: usize = buckets.iter().map(|n| n * n).sum();
+            // Prefer guesses that could be the answer when tied
+            let not_candidate = candidates.binary_search(&guess).is_err();
+            (expected, not_candidate, guess)
+        })
+        .collect::<Vec<_>>();
+    ranked.sort_unstable();
+
+    ranked
+        .into_iter()
+        .take(count)
+        .map(|(_, _, guess)| guess)
+        .collect()
+}
+
+// Allocation-free equivalent of `scoring::score` that encodes the pattern as a base 3 number
+fn pattern_code(guess: &[u8], answer: &[u8]) -> usize {
+    let mut code = [0_u8; WORD_LENGTH]; // 0 = incorrect, 1 = contains, 2 = correct
+    let mut unmatched = [0_u8; 26];
+
+    for i in 0..WORD_LENGTH {
+        if guess[i] == answer[i] {
+            code[i] = 2;
+        } else {
+            unmatched[(answer[i] - b'a') as usize] += 1;
+        }
+    }
+
+    for i in 0..WORD_LENGTH {
+        let idx = (guess[i] - b'a') as usize;
+        if code[i] == 0 && unmatched[idx] > 0 {
+            unmatched[idx] -= 1;
+            code[i] = 1;
+        }
+    }
+
+    code.iter().fold(0, |acc, c| acc * 3 + *c as usize)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pattern_fixes_letters_by_position() {
+        let constraints = Constraints::default().with_pattern("cr_n_").unwrap();
+        assert!(constraints.matches("crane"));
+        assert!(constraints.matches("crone"));
+        assert!(!constraints.matches("brine"));
+
+        assert!(Constraints::default().with_pattern("cr_n").is_err());
+        assert!(Constraints::default().with_pattern("cr1n_").is_err());
+    }
+
+    #[test]
+    fn has_lacks_and_not_at() {
+        let constraints = Constraints::default()
+            .with_has("a")
+            .with_lacks("rt")
+            .with_not_at("e:5")
+            .unwrap();
+        assert!(constraints.matches("ideal"));
+        assert!(!constraints.matches("abide")); // E at position 5
+        assert!(!constraints.matches("crane")); // Has R
+        assert!(!constraints.matches("noise")); // No A
+
+        assert!(Constraints::default().with_not_at("e:6").is_err());
+        assert!(Constraints::default().with_not_at("e").is_err());
+    }
+
+    #[test]
+    fn guesses_must_give_the_same_feedback() {
+        let constraints = Constraints::default().with_guess("crane:..Y.G").unwrap();
+        assert!(constraints.matches("abide"));
+        assert!(!constraints.matches("crane"));
+        assert!(!constraints.matches("those"));
+
+        assert!(Constraints::default().with_guess("crane:..Y.").is_err());
+        assert!(Constraints::default().with_guess("crane:..Z.G").is_err());
+        assert!(Constraints::default().with_guess("crane").is_err());
+    }
+
+    #[test]
+    fn candidates_come_from_the_pool() {
+        let constraints = Constraints::default().with_guess("crane:GGGGG").unwrap();
+        assert_eq!(candidates(&answers(), &constraints), vec!["crane"]);
+    }
+}
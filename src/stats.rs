@@ -0,0 +1,113 @@
+use std::{
+    env,
+    fs::{self, OpenOptions},
+    io::Write,
+    path::PathBuf,
+    time::Duration,
+};
+
+use anyhow::{Context, Result};
+use serde_json::{json, Value};
+use time::OffsetDateTime;
+
+use crate::{comp::timer::format_time, share, Outcome, ResultGrid};
+
+const STATS_FILE: &str = "stats.jsonl";
+
+// Directory for saved data, overridable with `TURDLE_DATA_DIR`
+pub fn data_dir() -> Result<PathBuf> {
+    if let Some(dir) = env::var_os("TURDLE_DATA_DIR") {
+        return Ok(PathBuf::from(dir));
+    }
+
+    let dir = dirs::data_dir().context("Could not find a data directory")?;
+    Ok(dir.join(crate::APP_NAME))
+}
+
+// Appends a record to the game history, one JSON object per line
+pub fn record(mut entry: Value) -> Result<()> {
+    let dir = data_dir()?;
+    fs::create_dir_all(&dir)?;
+
+    entry["date"] = json!(OffsetDateTime::now_utc().unix_timestamp());
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(dir.join(STATS_FILE))?;
+    writeln!(file, "{}", entry)?;
+
+    Ok(())
+}
+
+// Saves a finished game, warning rather than failing if the history can't be written
+pub fn record_game(rg: &ResultGrid, outcome: Outcome) {
+    let mut entry = share::report(rg, outcome);
+    entry["type"] = json!("game");
+    if let Err(e) = record(entry) {
+        eprintln!("(Could not save stats: {})", e);
+    }
+}
+
+pub fn record_marathon(limit: Duration, solved: usize, time: Duration, games: &[ResultGrid]) {
+    let entry = json!({
+        "type": "marathon",
+        "limit_secs": limit.as_secs(),
+        "solved": solved,
+        "time_secs": time.as_secs_f64(),
+        "answers": games.iter().map(|rg| &rg.solution.answer).collect::<Vec<_>>(),
+    });
+    if let Err(e) = record(entry) {
+        eprintln!("(Could not save stats: {})", e);
+    }
+}
+
+pub fn load() -> Result<Vec<Value>> {
+    let path = data_dir()?.join(STATS_FILE);
+    if !path.exists() {
+        return Ok(vec![]);
+    }
+
+    // Skip lines that can't be read rather than losing the whole history
+    let entries = fs::read_to_string(path)?
+        .lines()
+        .filter_map(|line| serde_json::from_str(line).ok())
+        .collect();
+
+    Ok(entries)
+}
+
+// Prints a summary of the game history
+pub fn summary() -> Result<()> {
+    let entries = load()?;
+    let games: Vec<&Value> = entries.iter().filter(|e| e["type"] == "game").collect();
+    let won: Vec<&&Value> = games.iter().filter(|e| e["won"] == true).collect();
+
+    println!("Played:    {}", games.len());
+    println!("Won:       {}", won.len());
+    if !games.is_empty() {
+        println!("Win rate:  {}%", won.len() * 100 / games.len());
+    }
+
+    // Timed wins
+    let times: Vec<Duration> = won
+        .iter()
+        .filter_map(|e| e["time_secs"].as_f64())
+        .map(Duration::from_secs_f64)
+        .collect();
+    if let Some(best) = times.iter().min() {
+        let total: Duration = times.iter().sum();
+        println!("Best time: {}", format_time(*best));
+        println!("Avg. time: {}", format_time(total / times.len() as u32));
+    }
+
+    let marathon_best = entries
+        .iter()
+        .filter(|e| e["type"] == "marathon")
+        .filter_map(|e| e["solved"].as_u64())
+        .max();
+    if let Some(best) = marathon_best {
+        println!("Marathon:  {} solved (best)", best);
+    }
+
+    Ok(())
+}
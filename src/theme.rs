@@ -5,10 +5,18 @@ pub const CELL_BG_EMPTY: Color = Color::Indexed(239);
 pub const CELL_BG_INCORRECT: Color = Color::Indexed(236);
 pub const CELL_BG_CONTAINS: Color = Color::Indexed(214);
 pub const CELL_BG_CORRECT: Color = Color::Indexed(2);
+pub const CELL_BORDER_SELECTED: Color = Color::White;
+pub const CELL_BORDER_LIE: Color = Color::Indexed(196);
 
 pub const LETTER_FG: Color = CELL_FG;
 pub const LETTER_BG_UNUSED: Color = CELL_BG_EMPTY;
 pub const LETTER_FG_INCORRECT: Color = Color::Indexed(241);
 pub const LETTER_BG_INCORRECT: Color = CELL_BG_INCORRECT;
 pub const LETTER_BG_CONTAINS: Color = CELL_BG_CONTAINS;
-pub const LETTER_BG_CORRECT: Color = CELL_BG_CORRECT;
\ No newline at end of file
+pub const LETTER_BG_CORRECT: Color = CELL_BG_CORRECT;
+
+pub const STATUS_FG: Color = Color::Indexed(245);
+pub const TIMER_FG_LOW: Color = Color::Indexed(196);
+
+// Hot-seat players' names and row labels
+pub const PLAYER_FG: [Color; 2] = [Color::Indexed(39), Color::Indexed(205)];
@@ -0,0 +1,114 @@
+use std::{
+    io::{self, BufRead, Write},
+    process::ExitCode,
+};
+
+use anyhow::{bail, Result};
+use serde_json::json;
+
+use crate::{
+    engine::Variant,
+    scoring::{self, is_valid_word, WORD_LENGTH},
+    share::{self, ShareFormat},
+    solver::{self, Constraints},
+};
+
+// Exit code for an invalid guess or no candidates, kept apart from errors (1) so scripts can
+// tell them apart
+const NO_MATCH: u8 = 5;
+
+// Scores each guess against the answer, reading guesses from stdin if none are given
+pub fn check(answer: &str, guesses: &[String], format: ShareFormat) -> Result<ExitCode> {
+    let answer = answer.to_ascii_lowercase();
+    if answer.len() != WORD_LENGTH || !answer.chars().all(|c| c.is_ascii_lowercase()) {
+        bail!("Answer must be a {} letter word", WORD_LENGTH);
+    }
+
+    let guesses = if guesses.is_empty() {
+        let mut words = vec![];
+        for line in io::stdin().lock().lines() {
+            words.extend(line?.split_whitespace().map(String::from));
+        }
+        words
+    } else {
+        guesses.to_vec()
+    };
+
+    let mut out = io::stdout().lock();
+    let mut all_valid = true;
+    for guess in guesses.iter().map(|g| g.to_ascii_lowercase()) {
+        let valid = is_valid_word(&guess);
+        let states = if valid {
+            scoring::score(&guess, &answer)
+        } else {
+            all_valid = false;
+            vec![]
+        };
+
+        if format == ShareFormat::Json {
+            let line = json!({
+                "guess": guess,
+                "valid": valid,
+                "pattern": if valid { Some(share::pattern(&states)) } else { None },
+            });
+            if !print_line(&mut out, &line.to_string()) {
+                break;
+            }
+        } else if valid {
+            let line = format!(
+                "{} {}",
+                guess.to_ascii_uppercase(),
+                share::tiles(&states, format, Variant::Classic)
+            );
+            if !print_line(&mut out, &line) {
+                break;
+            }
+        } else {
+            eprintln!("\"{}\" is not in the word list", guess);
+        }
+    }
+
+    Ok(if all_valid {
+        ExitCode::SUCCESS
+    } else {
+        ExitCode::from(NO_MATCH)
+    })
+}
+
+// Lists the words matching the constraints
+pub fn search(
+    constraints: &Constraints,
+    all_words: bool,
+    by_frequency: bool,
+    limit: Option<usize>,
+) -> Result<ExitCode> {
+    let pool = if all_words {
+        solver::all_words()
+    } else {
+        solver::answers()
+    };
+
+    let mut words = solver::candidates(&pool, constraints);
+    if by_frequency {
+        solver::sort_by_frequency(&mut words);
+    }
+
+    let mut out = io::stdout().lock();
+    for word in words.iter().take(limit.unwrap_or(usize::MAX)) {
+        if !print_line(&mut out, word) {
+            break;
+        }
+    }
+    eprintln!("{} candidate(s)", words.len());
+
+    Ok(if words.is_empty() {
+        ExitCode::from(NO_MATCH)
+    } else {
+        ExitCode::SUCCESS
+    })
+}
+
+// Returns false once stdout has been closed, e.g. when piped into `head`
+fn print_line(out: &mut impl Write, line: &str) -> bool {
+    writeln!(out, "{}", line).is_ok()
+}